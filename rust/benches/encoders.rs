@@ -0,0 +1,120 @@
+//! Compares the built-in serde based encoders and decoders.
+//!
+//! Each format is benchmarked on the same event, and the benchmark id carries
+//! the encoded payload size so that speed and compactness can be compared.
+//! `encode_pooled` encodes into buffers of a [`BufferPool`] like a
+//! [`Pub`](object_transfer::Pub) configured with a pool does, to compare
+//! against the allocating `encode` path.
+//!
+//! Run with `cargo bench --all-features --bench encoders`.
+
+use ::std::hint::black_box;
+
+use ::criterion::{
+  BenchmarkId, Criterion, Throughput, criterion_group, criterion_main,
+};
+use ::serde::{Deserialize, Serialize};
+
+use ::object_transfer::BufferPool;
+use ::object_transfer::encoders::{
+  BincodeDecoder, BincodeEncoder, Decoder, Encoder, JSONDecoder, JSONEncoder,
+  MessagePackDecoder, MessagePackEncoder, PostcardDecoder, PostcardEncoder,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Event {
+  id: u64,
+  kind: String,
+  score: f64,
+  active: bool,
+  tags: Vec<String>,
+  payload: Vec<u32>,
+}
+
+impl Event {
+  fn sample() -> Self {
+    Self {
+      id: 42_424_242,
+      kind: "user.created".to_string(),
+      score: 0.75,
+      active: true,
+      tags: vec!["alpha".into(), "beta".into(), "gamma".into()],
+      payload: (0..64).collect(),
+    }
+  }
+}
+
+fn bench_codec<E, D>(c: &mut Criterion, name: &str, encoder: E, decoder: D)
+where
+  E: Encoder<Item = Event>,
+  D: Decoder<Item = Event>,
+{
+  let event = Event::sample();
+  let payload = encoder.encode(&event).unwrap();
+  assert_eq!(decoder.decode(payload.clone()).unwrap(), event);
+
+  let mut group = c.benchmark_group(name);
+  group.throughput(Throughput::Bytes(payload.len() as u64));
+  group.bench_with_input(
+    BenchmarkId::new("encode", payload.len()),
+    &event,
+    |b, event| b.iter(|| encoder.encode(black_box(event)).unwrap()),
+  );
+  let pool = BufferPool::new();
+  group.bench_with_input(
+    BenchmarkId::new("encode_pooled", payload.len()),
+    &event,
+    |b, event| {
+      b.iter(|| {
+        let mut buf = pool.acquire();
+        encoder.encode_into(black_box(event), &mut buf).unwrap();
+        let payload = buf.split().freeze();
+        pool.release(buf);
+        payload
+      })
+    },
+  );
+  group.bench_with_input(
+    BenchmarkId::new("decode", payload.len()),
+    &payload,
+    |b, payload| {
+      b.iter(|| decoder.decode(black_box(payload.clone())).unwrap())
+    },
+  );
+  group.finish();
+}
+
+fn bench_json(c: &mut Criterion) {
+  bench_codec(c, "json", JSONEncoder::new(), JSONDecoder::new());
+}
+
+fn bench_msgpack(c: &mut Criterion) {
+  bench_codec(
+    c,
+    "msgpack",
+    MessagePackEncoder::new(),
+    MessagePackDecoder::new(),
+  );
+}
+
+fn bench_bincode(c: &mut Criterion) {
+  bench_codec(c, "bincode", BincodeEncoder::new(), BincodeDecoder::new());
+}
+
+fn bench_postcard(c: &mut Criterion) {
+  bench_codec(
+    c,
+    "postcard",
+    PostcardEncoder::new(),
+    PostcardDecoder::new(),
+  );
+}
+
+criterion_group!(
+  benches,
+  bench_json,
+  bench_msgpack,
+  bench_bincode,
+  bench_postcard
+);
+criterion_main!(benches);
@@ -19,6 +19,13 @@
 //! - [`nats`]: NATS JetStream broker implementation for high-performance message streaming.
 //! - [`redis`]: Redis Streams broker implementation for persistent message queuing.
 //!
+//! # Wrappers
+//!
+//! - [`claim_check`]: Publishes oversized payloads by reference to a blob store
+//!   (requires the `claimcheck` feature).
+//! - [`chunked`]: Splits large payloads into chunks reassembled by the subscriber
+//!   (requires the `chunked` feature).
+//!
 //! # Error Handling
 //!
 //! The [`errors`] submodule provides broker-specific and common error types that may be
@@ -55,6 +62,10 @@
 //! }
 //! ```
 
+#[cfg(feature = "chunked")]
+pub mod chunked;
+#[cfg(feature = "claimcheck")]
+pub mod claim_check;
 pub mod errors;
 #[cfg(feature = "nats")]
 pub mod nats;
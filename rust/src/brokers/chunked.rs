@@ -0,0 +1,48 @@
+//! Chunked transfer of large payloads.
+//!
+//! As an alternative to the [claim-check](super::claim_check) wrappers, which
+//! need an external blob store, the [`Publisher`] splits payloads above the
+//! chunk size into sequenced chunks published on the same topic, and the
+//! [`Subscriber`] reassembles them before handing the payload over.
+//!
+//! Each chunk carries the CRC32 checksum of the whole payload, which is
+//! verified after reassembly. The chunks of a payload are acknowledged only
+//! once the reassembled payload is acknowledged, so that the broker
+//! redelivers the whole set if processing fails. Incomplete sets are dropped
+//! after a timeout, and reported as [`ChunkError::Timeout`]; like rejected
+//! transfers, their chunks are acknowledged only if
+//! [`Subscriber::ack_rejected`] is enabled. The memory used for reassembly is bounded by the
+//! maximum number of chunks, payload size and concurrent transfers of the
+//! [`Subscriber`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use object_transfer::brokers::{PubBrokerTrait, SubBrokerTrait};
+//! use object_transfer::brokers::chunked::{Publisher, Subscriber};
+//!
+//! fn wrap(
+//!     pub_broker: Arc<dyn PubBrokerTrait + Send + Sync>,
+//!     sub_broker: Arc<dyn SubBrokerTrait + Send + Sync>,
+//! ) -> (Publisher, Subscriber) {
+//!     (
+//!         Publisher::new(pub_broker).chunk_size(256 * 1024),
+//!         Subscriber::new(sub_broker).timeout(Duration::from_secs(30)),
+//!     )
+//! }
+//! ```
+
+mod ack;
+mod chunk;
+mod errors;
+mod publisher;
+mod subscriber;
+
+pub use self::errors::ChunkError;
+pub use self::publisher::{DEFAULT_CHUNK_SIZE, Publisher};
+pub use self::subscriber::{
+  DEFAULT_MAX_CHUNKS, DEFAULT_MAX_PAYLOAD, DEFAULT_MAX_TRANSFERS,
+  DEFAULT_TIMEOUT, Subscriber,
+};
@@ -0,0 +1,26 @@
+use ::std::sync::Arc;
+
+use ::async_trait::async_trait;
+use ::futures::future::try_join_all;
+
+use crate::errors::AckError;
+use crate::traits::AckTrait;
+
+/// Acknowledgment of a reassembled payload, acknowledging all its chunks.
+pub(super) struct Ack {
+  chunks: Vec<Arc<dyn AckTrait + Send + Sync>>,
+}
+
+impl Ack {
+  pub(super) fn new(chunks: Vec<Arc<dyn AckTrait + Send + Sync>>) -> Self {
+    Self { chunks }
+  }
+}
+
+#[async_trait]
+impl AckTrait for Ack {
+  async fn ack(&self) -> Result<(), AckError> {
+    try_join_all(self.chunks.iter().map(|ack| ack.ack())).await?;
+    Ok(())
+  }
+}
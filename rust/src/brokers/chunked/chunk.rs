@@ -0,0 +1,129 @@
+//! Wire format of the chunks.
+//!
+//! | Bytes    | Content                                      |
+//! |----------|----------------------------------------------|
+//! | 0..M     | Chunk marker                                 |
+//! | M..M+16  | Transfer ID (UUID)                           |
+//! | +0..4    | Index of the chunk (big-endian `u32`)        |
+//! | +4..8    | Number of chunks (big-endian `u32`)          |
+//! | +8..12   | CRC32 of the whole payload (big-endian `u32`)|
+//! | +12..    | Chunk data                                   |
+//!
+//! The marker starts with `0xC1`, which is neither valid UTF-8 nor a valid
+//! MessagePack byte, so it doesn't collide with regular payloads.
+
+use ::bytes::{Buf, BufMut, Bytes, BytesMut};
+use ::uuid::Uuid;
+
+use super::errors::ChunkError;
+
+/// Marker prefixing every chunk.
+const MARKER: &[u8] = b"\xC1\xC1object-transfer/chunk:";
+
+/// Length of the header following the marker.
+const HEADER_LEN: usize = 16 + 4 + 4 + 4;
+
+/// A chunk of a payload.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Chunk {
+  pub(super) transfer_id: Uuid,
+  pub(super) index: u32,
+  pub(super) total: u32,
+  pub(super) checksum: u32,
+  pub(super) data: Bytes,
+}
+
+impl Chunk {
+  /// Serializes the chunk into a message payload.
+  pub(super) fn encode(&self) -> Bytes {
+    let mut buf =
+      BytesMut::with_capacity(MARKER.len() + HEADER_LEN + self.data.len());
+    buf.put_slice(MARKER);
+    buf.put_slice(self.transfer_id.as_bytes());
+    buf.put_u32(self.index);
+    buf.put_u32(self.total);
+    buf.put_u32(self.checksum);
+    buf.put_slice(&self.data);
+    buf.freeze()
+  }
+
+  /// Parses a message payload.
+  ///
+  /// # Returns
+  /// `None` if `payload` isn't a chunk.
+  pub(super) fn decode(payload: &Bytes) -> Option<Result<Self, ChunkError>> {
+    if !payload.starts_with(MARKER) {
+      return None;
+    }
+    let mut header = payload.slice(MARKER.len()..);
+    if header.len() < HEADER_LEN {
+      return Some(Err(ChunkError::MalformedHeader));
+    }
+    let mut transfer_id = [0; 16];
+    header.copy_to_slice(&mut transfer_id);
+    let transfer_id = Uuid::from_bytes(transfer_id);
+    let index = header.get_u32();
+    let total = header.get_u32();
+    let checksum = header.get_u32();
+    if index >= total {
+      return Some(Err(ChunkError::MalformedHeader));
+    }
+    Some(Ok(Self {
+      transfer_id,
+      index,
+      total,
+      checksum,
+      data: header,
+    }))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_round_trip() {
+    let chunk = Chunk {
+      transfer_id: Uuid::new_v4(),
+      index: 1,
+      total: 3,
+      checksum: 0xDEADBEEF,
+      data: Bytes::from_static(b"data"),
+    };
+
+    let decoded = Chunk::decode(&chunk.encode()).unwrap().unwrap();
+
+    assert_eq!(decoded, chunk);
+  }
+
+  #[test]
+  fn test_regular_payload() {
+    assert!(Chunk::decode(&Bytes::from_static(b"{\"id\": 1}")).is_none());
+  }
+
+  #[test]
+  fn test_truncated_header() {
+    let mut payload = BytesMut::from(MARKER);
+    payload.put_u32(0);
+
+    let result = Chunk::decode(&payload.freeze()).unwrap();
+
+    assert!(matches!(result, Err(ChunkError::MalformedHeader)));
+  }
+
+  #[test]
+  fn test_index_out_of_range() {
+    let chunk = Chunk {
+      transfer_id: Uuid::new_v4(),
+      index: 3,
+      total: 3,
+      checksum: 0,
+      data: Bytes::new(),
+    };
+
+    let result = Chunk::decode(&chunk.encode()).unwrap();
+
+    assert!(matches!(result, Err(ChunkError::MalformedHeader)));
+  }
+}
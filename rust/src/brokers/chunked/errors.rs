@@ -0,0 +1,40 @@
+use ::thiserror::Error;
+
+use crate::errors::BrokerError;
+
+/// Errors that can occur while reassembling chunked payloads.
+#[derive(Error, Debug)]
+pub enum ChunkError {
+  /// The message carries the chunk marker, but its header is invalid.
+  #[error("Malformed chunk header")]
+  MalformedHeader,
+  /// The header of a chunk contradicts the other chunks of its transfer.
+  #[error("Inconsistent chunk in transfer {0}")]
+  Inconsistent(String),
+  /// The chunks of a transfer didn't all arrive before the timeout.
+  #[error("Transfer {0} timed out before all chunks arrived")]
+  Timeout(String),
+  /// The reassembled payload doesn't match the checksum of the transfer.
+  #[error("Checksum mismatch in transfer {0}")]
+  ChecksumMismatch(String),
+  /// The transfer has more chunks than the subscriber accepts.
+  #[error("Transfer {transfer_id} has {total} chunks, at most {max} allowed")]
+  TooManyChunks {
+    transfer_id: String,
+    total: u32,
+    max: u32,
+  },
+  /// The chunks of the transfer exceed the payload size the subscriber
+  /// accepts.
+  #[error("Transfer {0} exceeds the maximum payload size")]
+  PayloadTooLarge(String),
+  /// The subscriber is already reassembling the maximum number of transfers.
+  #[error("Too many transfers in flight to start transfer {0}")]
+  TooManyTransfers(String),
+}
+
+impl From<ChunkError> for BrokerError {
+  fn from(err: ChunkError) -> Self {
+    BrokerError::new(err)
+  }
+}
@@ -0,0 +1,150 @@
+use ::std::sync::Arc;
+
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::uuid::Uuid;
+
+use super::super::traits::PubBrokerTrait;
+use crate::errors::BrokerError;
+
+use super::chunk::Chunk;
+
+/// Default maximum size (in bytes) of the data carried by each chunk.
+///
+/// Half of the default maximum payload of NATS (1 MiB).
+pub const DEFAULT_CHUNK_SIZE: usize = 512 * 1024;
+
+/// Publisher splitting large payloads into chunks.
+///
+/// Payloads larger than the chunk size are published as a sequence of
+/// chunks; smaller payloads are published as-is.
+#[derive(Clone)]
+pub struct Publisher {
+  inner: Arc<dyn PubBrokerTrait + Send + Sync>,
+  chunk_size: usize,
+}
+
+impl Publisher {
+  /// Creates a new chunking publisher with [`DEFAULT_CHUNK_SIZE`].
+  ///
+  /// # Parameters
+  /// - `inner`: Broker publishing the payloads and chunks.
+  pub fn new(inner: Arc<dyn PubBrokerTrait + Send + Sync>) -> Self {
+    Self {
+      inner,
+      chunk_size: DEFAULT_CHUNK_SIZE,
+    }
+  }
+
+  /// Sets the maximum size of the data carried by each chunk.
+  ///
+  /// # Parameters
+  /// - `chunk_size`: The size in bytes.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  ///
+  /// # Panics
+  /// Panics if `chunk_size` is zero.
+  pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+    assert!(chunk_size > 0, "chunk size must be greater than zero");
+    self.chunk_size = chunk_size;
+    self
+  }
+}
+
+#[async_trait]
+impl PubBrokerTrait for Publisher {
+  /// Publishes `payload`, split into chunks if it exceeds the chunk size.
+  ///
+  /// The chunks are published in order. If publishing a chunk fails, the
+  /// remaining chunks aren't published, and the subscriber drops the
+  /// incomplete set after its timeout.
+  async fn publish(
+    &self,
+    topic: &str,
+    payload: Bytes,
+  ) -> Result<(), BrokerError> {
+    if payload.len() <= self.chunk_size {
+      return self.inner.publish(topic, payload).await;
+    }
+    let transfer_id = Uuid::new_v4();
+    let checksum = ::crc32fast::hash(&payload);
+    let total = payload.len().div_ceil(self.chunk_size);
+    let total = u32::try_from(total).map_err(BrokerError::new)?;
+    for index in 0..total {
+      let start = index as usize * self.chunk_size;
+      let end = (start + self.chunk_size).min(payload.len());
+      let chunk = Chunk {
+        transfer_id,
+        index,
+        total,
+        checksum,
+        data: payload.slice(start..end),
+      };
+      self.inner.publish(topic, chunk.encode()).await?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::std::sync::Mutex;
+
+  use super::*;
+  use crate::brokers::traits::MockPubBrokerTrait;
+
+  fn capture() -> (Arc<Mutex<Vec<Bytes>>>, MockPubBrokerTrait) {
+    let published = Arc::new(Mutex::new(Vec::new()));
+    let mut inner = MockPubBrokerTrait::new();
+    let sink = published.clone();
+    inner.expect_publish().returning(move |_, payload| {
+      sink.lock().unwrap().push(payload);
+      Ok(())
+    });
+    (published, inner)
+  }
+
+  #[tokio::test]
+  async fn test_small_payload_is_not_chunked() {
+    let (published, inner) = capture();
+    let publisher = Publisher::new(Arc::new(inner)).chunk_size(4);
+
+    publisher
+      .publish("topic", Bytes::from_static(b"1234"))
+      .await
+      .unwrap();
+
+    assert_eq!(
+      *published.lock().unwrap(),
+      vec![Bytes::from_static(b"1234")]
+    );
+  }
+
+  #[tokio::test]
+  async fn test_large_payload_is_chunked() {
+    let (published, inner) = capture();
+    let publisher = Publisher::new(Arc::new(inner)).chunk_size(4);
+
+    publisher
+      .publish("topic", Bytes::from_static(b"0123456789"))
+      .await
+      .unwrap();
+
+    let chunks: Vec<_> = published
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|payload| Chunk::decode(payload).unwrap().unwrap())
+      .collect();
+    let data: Vec<_> = chunks.iter().map(|c| c.data.clone()).collect();
+    assert_eq!(data, vec!["0123", "4567", "89"]);
+    assert!(chunks.iter().enumerate().all(|(i, c)| {
+      c.index == i as u32
+        && c.total == 3
+        && c.transfer_id == chunks[0].transfer_id
+        && c.checksum == ::crc32fast::hash(b"0123456789")
+    }));
+  }
+}
@@ -0,0 +1,590 @@
+use ::std::collections::HashMap;
+use ::std::pin::pin;
+use ::std::sync::Arc;
+use ::std::time::{Duration, Instant};
+
+use ::async_stream::stream;
+use ::async_trait::async_trait;
+use ::bytes::{Bytes, BytesMut};
+use ::futures::future::{Either, pending, select};
+use ::futures::stream::{BoxStream, StreamExt};
+use ::tokio::time::sleep_until;
+use ::uuid::Uuid;
+
+use super::super::traits::SubBrokerTrait;
+use crate::errors::BrokerError;
+use crate::traits::AckTrait;
+
+use super::ack::Ack;
+use super::chunk::Chunk;
+use super::errors::ChunkError;
+
+/// Default time to wait for the missing chunks of a payload.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Default maximum number of chunks of a payload.
+pub const DEFAULT_MAX_CHUNKS: u32 = 4096;
+/// Default maximum size of a reassembled payload, in bytes.
+pub const DEFAULT_MAX_PAYLOAD: usize = 64 * 1024 * 1024;
+/// Default maximum number of payloads reassembled at the same time.
+pub const DEFAULT_MAX_TRANSFERS: usize = 16;
+
+type Message = (Bytes, Arc<dyn AckTrait + Send + Sync>);
+
+/// Subscriber reassembling the chunks published by the chunking
+/// [`Publisher`](super::Publisher).
+///
+/// Messages that aren't chunks are passed through unchanged. Incomplete
+/// transfers expire when their timeout elapses, even when no message arrives.
+///
+/// The chunks of a payload stay unacknowledged until the payload is
+/// acknowledged, so with JetStream they count against the `max_ack_pending`
+/// limit of the consumer: it must leave room for the chunks of
+/// `max_transfers` payloads, or the consumer stalls before any of them
+/// completes.
+///
+/// Chunks announcing more than `max_chunks` chunks, transfers exceeding
+/// `max_payload` bytes, and chunks starting a transfer while `max_transfers`
+/// are in flight are rejected with a [`ChunkError`] before anything is
+/// buffered. The chunks of rejected and expired transfers are left
+/// unacknowledged unless [`Subscriber::ack_rejected`] is enabled.
+#[derive(Clone)]
+pub struct Subscriber {
+  inner: Arc<dyn SubBrokerTrait + Send + Sync>,
+  timeout: Duration,
+  limits: Limits,
+  ack_rejected: bool,
+}
+
+/// Bounds of the memory used for reassembly.
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+  max_chunks: u32,
+  max_payload: usize,
+  max_transfers: usize,
+}
+
+impl Subscriber {
+  /// Creates a new reassembling subscriber with [`DEFAULT_TIMEOUT`].
+  ///
+  /// # Parameters
+  /// - `inner`: Broker delivering the payloads and chunks.
+  pub fn new(inner: Arc<dyn SubBrokerTrait + Send + Sync>) -> Self {
+    Self {
+      inner,
+      timeout: DEFAULT_TIMEOUT,
+      limits: Limits {
+        max_chunks: DEFAULT_MAX_CHUNKS,
+        max_payload: DEFAULT_MAX_PAYLOAD,
+        max_transfers: DEFAULT_MAX_TRANSFERS,
+      },
+      ack_rejected: false,
+    }
+  }
+
+  /// Sets the time to wait for the missing chunks of a payload.
+  ///
+  /// # Parameters
+  /// - `timeout`: Time since the first received chunk of a payload.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// Sets the maximum number of chunks of a payload. Defaults to
+  /// [`DEFAULT_MAX_CHUNKS`].
+  ///
+  /// # Parameters
+  /// - `max_chunks`: Chunks announcing more chunks are rejected.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn max_chunks(mut self, max_chunks: u32) -> Self {
+    self.limits.max_chunks = max_chunks;
+    self
+  }
+
+  /// Sets the maximum size of a reassembled payload. Defaults to
+  /// [`DEFAULT_MAX_PAYLOAD`].
+  ///
+  /// # Parameters
+  /// - `max_payload`: Maximum number of bytes buffered per transfer.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn max_payload(mut self, max_payload: usize) -> Self {
+    self.limits.max_payload = max_payload;
+    self
+  }
+
+  /// Sets the maximum number of payloads reassembled at the same time.
+  /// Defaults to [`DEFAULT_MAX_TRANSFERS`].
+  ///
+  /// # Parameters
+  /// - `max_transfers`: Chunks starting another transfer are rejected.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn max_transfers(mut self, max_transfers: usize) -> Self {
+    self.limits.max_transfers = max_transfers;
+    self
+  }
+
+  /// Sets whether the chunks of rejected and expired transfers are
+  /// acknowledged before the [`ChunkError`] is yielded.
+  ///
+  /// Enable it so that the broker doesn't redeliver malformed, corrupted or
+  /// oversized transfers forever. Disabled by default, leaving the chunks to
+  /// be redelivered.
+  ///
+  /// # Parameters
+  /// - `ack_rejected`: If true, the chunks of rejected transfers are
+  ///   acknowledged.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn ack_rejected(mut self, ack_rejected: bool) -> Self {
+    self.ack_rejected = ack_rejected;
+    self
+  }
+}
+
+/// Acknowledges the chunks of a rejected transfer, if enabled, and converts
+/// the rejection into the error to yield.
+async fn reject(
+  ack_rejected: bool,
+  err: ChunkError,
+  acks: Vec<Arc<dyn AckTrait + Send + Sync>>,
+) -> BrokerError {
+  if ack_rejected && let Err(ack_err) = Ack::new(acks).ack().await {
+    return BrokerError::new(ack_err);
+  }
+  err.into()
+}
+
+#[async_trait]
+impl SubBrokerTrait for Subscriber {
+  async fn subscribe(
+    &self,
+  ) -> Result<
+    BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+    BrokerError,
+  > {
+    let mut messages = self.inner.subscribe().await?;
+    let mut reassembler = Reassembler::new(self.timeout, self.limits);
+    let ack_rejected = self.ack_rejected;
+    let reassembled = stream! {
+      loop {
+        let deadline = reassembler.next_deadline();
+        let expiry = async move {
+          match deadline {
+            Some(deadline) => sleep_until(deadline.into()).await,
+            None => pending().await,
+          }
+        };
+        let msg = match select(messages.next(), pin!(expiry)).await {
+          Either::Left((Some(msg), _)) => Some(msg),
+          Either::Left((None, _)) => break,
+          Either::Right(_) => None,
+        };
+        for (transfer_id, acks) in reassembler.expire(Instant::now()) {
+          let err = ChunkError::Timeout(transfer_id.to_string());
+          yield Err(reject(ack_rejected, err, acks).await);
+        }
+        let Some(msg) = msg else {
+          continue;
+        };
+        let (payload, ack) = match msg {
+          Ok(msg) => msg,
+          Err(err) => {
+            yield Err(err);
+            continue;
+          }
+        };
+        match Chunk::decode(&payload) {
+          None => yield Ok((payload, ack)),
+          Some(Err(err)) => yield Err(reject(ack_rejected, err, vec![ack]).await),
+          Some(Ok(chunk)) => match reassembler.push(chunk, ack) {
+            Ok(Some(msg)) => yield Ok(msg),
+            Ok(None) => {}
+            Err((err, acks)) => yield Err(reject(ack_rejected, err, acks).await),
+          },
+        }
+      }
+    };
+    Ok(reassembled.boxed())
+  }
+}
+
+/// Rejected chunk, with the acknowledgments of the chunks it discards.
+type Rejection = (ChunkError, Vec<Arc<dyn AckTrait + Send + Sync>>);
+
+/// Chunks received so far for a payload.
+struct Transfer {
+  total: u32,
+  checksum: u32,
+  started: Instant,
+  size: usize,
+  chunks: Vec<Option<Bytes>>,
+  acks: Vec<Arc<dyn AckTrait + Send + Sync>>,
+}
+
+impl Transfer {
+  fn is_complete(&self) -> bool {
+    self.chunks.iter().all(Option::is_some)
+  }
+}
+
+/// Collects chunks until their payload is complete.
+struct Reassembler {
+  timeout: Duration,
+  limits: Limits,
+  transfers: HashMap<Uuid, Transfer>,
+}
+
+impl Reassembler {
+  fn new(timeout: Duration, limits: Limits) -> Self {
+    Self {
+      timeout,
+      limits,
+      transfers: HashMap::new(),
+    }
+  }
+
+  /// Returns the time at which the oldest incomplete transfer expires.
+  fn next_deadline(&self) -> Option<Instant> {
+    self
+      .transfers
+      .values()
+      .map(|t| t.started + self.timeout)
+      .min()
+  }
+
+  /// Drops the incomplete transfers started more than the timeout ago.
+  ///
+  /// # Returns
+  /// The IDs of the dropped transfers, with the acknowledgments of their
+  /// buffered chunks.
+  fn expire(
+    &mut self,
+    now: Instant,
+  ) -> Vec<(Uuid, Vec<Arc<dyn AckTrait + Send + Sync>>)> {
+    let expired: Vec<Uuid> = self
+      .transfers
+      .iter()
+      .filter(|(_, t)| now.duration_since(t.started) >= self.timeout)
+      .map(|(id, _)| *id)
+      .collect();
+    expired
+      .into_iter()
+      .filter_map(|id| Some((id, self.transfers.remove(&id)?.acks)))
+      .collect()
+  }
+
+  /// Adds a chunk to its transfer.
+  ///
+  /// Redelivered chunks are accepted, and acknowledged together with the
+  /// others. A transfer exceeding the maximum payload size is dropped.
+  ///
+  /// # Returns
+  /// The reassembled payload once all the chunks of the transfer arrived.
+  /// On rejection, the acknowledgments of the rejected chunk and of the
+  /// dropped transfer, if any, come with the error.
+  fn push(
+    &mut self,
+    chunk: Chunk,
+    ack: Arc<dyn AckTrait + Send + Sync>,
+  ) -> Result<Option<Message>, Rejection> {
+    let id = chunk.transfer_id;
+    if !self.transfers.contains_key(&id) {
+      if chunk.total > self.limits.max_chunks {
+        let err = ChunkError::TooManyChunks {
+          transfer_id: id.to_string(),
+          total: chunk.total,
+          max: self.limits.max_chunks,
+        };
+        return Err((err, vec![ack]));
+      }
+      if self.transfers.len() >= self.limits.max_transfers {
+        return Err((ChunkError::TooManyTransfers(id.to_string()), vec![ack]));
+      }
+    }
+    let transfer = self.transfers.entry(id).or_insert_with(|| Transfer {
+      total: chunk.total,
+      checksum: chunk.checksum,
+      started: Instant::now(),
+      size: 0,
+      chunks: vec![None; chunk.total as usize],
+      acks: Vec::new(),
+    });
+    if transfer.total != chunk.total || transfer.checksum != chunk.checksum {
+      return Err((ChunkError::Inconsistent(id.to_string()), vec![ack]));
+    }
+    let slot = &mut transfer.chunks[chunk.index as usize];
+    let size =
+      transfer.size - slot.as_ref().map_or(0, Bytes::len) + chunk.data.len();
+    if size > self.limits.max_payload {
+      let mut acks = self.transfers.remove(&id).unwrap().acks;
+      acks.push(ack);
+      return Err((ChunkError::PayloadTooLarge(id.to_string()), acks));
+    }
+    *slot = Some(chunk.data);
+    transfer.size = size;
+    transfer.acks.push(ack);
+    if !transfer.is_complete() {
+      return Ok(None);
+    }
+    let transfer = self.transfers.remove(&chunk.transfer_id).unwrap();
+    let mut payload = BytesMut::new();
+    for data in transfer.chunks.into_iter().flatten() {
+      payload.extend_from_slice(&data);
+    }
+    if ::crc32fast::hash(&payload) != transfer.checksum {
+      let err = ChunkError::ChecksumMismatch(chunk.transfer_id.to_string());
+      return Err((err, transfer.acks));
+    }
+    let ack: Arc<dyn AckTrait + Send + Sync> =
+      Arc::new(Ack::new(transfer.acks));
+    Ok(Some((payload.freeze(), ack)))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::std::sync::Mutex;
+
+  use ::futures::stream::TryStreamExt;
+
+  use super::super::Publisher;
+  use super::*;
+  use crate::brokers::PubBrokerTrait;
+  use crate::brokers::traits::MockPubBrokerTrait;
+  use crate::tests::subscribe::SubscribeMock;
+  use crate::traits::MockAckTrait;
+
+  fn ack(times: usize) -> Arc<dyn AckTrait + Send + Sync> {
+    let mut ack = MockAckTrait::new();
+    ack.expect_ack().times(times).returning(|| Ok(()));
+    Arc::new(ack)
+  }
+
+  async fn chunks(payload: &'static [u8]) -> Vec<Bytes> {
+    let published = Arc::new(Mutex::new(Vec::new()));
+    let mut inner = MockPubBrokerTrait::new();
+    let sink = published.clone();
+    inner.expect_publish().returning(move |_, payload| {
+      sink.lock().unwrap().push(payload);
+      Ok(())
+    });
+    Publisher::new(Arc::new(inner))
+      .chunk_size(4)
+      .publish("topic", Bytes::from_static(payload))
+      .await
+      .unwrap();
+    published.lock().unwrap().clone()
+  }
+
+  fn subscriber(data: Vec<Message>) -> Subscriber {
+    Subscriber::new(Arc::new(SubscribeMock::new(data)))
+  }
+
+  /// Delivers the messages, then waits forever.
+  struct Quiet(Mutex<Vec<Message>>);
+
+  #[async_trait]
+  impl SubBrokerTrait for Quiet {
+    async fn subscribe(
+      &self,
+    ) -> Result<
+      BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+      BrokerError,
+    > {
+      let data = std::mem::take(&mut *self.0.lock().unwrap());
+      let messages = ::futures::stream::iter(data.into_iter().map(Ok));
+      Ok(messages.chain(::futures::stream::pending()).boxed())
+    }
+  }
+
+  fn forged(total: u32) -> Bytes {
+    Chunk {
+      transfer_id: Uuid::new_v4(),
+      index: 0,
+      total,
+      checksum: 0,
+      data: Bytes::from_static(b"data"),
+    }
+    .encode()
+  }
+
+  #[tokio::test]
+  async fn test_reassembly() {
+    let mut chunks = chunks(b"0123456789").await;
+    chunks.swap(0, 2);
+    let mut data: Vec<Message> =
+      chunks.into_iter().map(|c| (c, ack(1))).collect();
+    data.insert(1, (Bytes::from_static(b"inline"), ack(1)));
+    let sub = subscriber(data);
+
+    let messages: Vec<_> =
+      sub.subscribe().await.unwrap().try_collect().await.unwrap();
+    for (_, ack) in &messages {
+      ack.ack().await.unwrap();
+    }
+
+    let payloads: Vec<_> = messages.into_iter().map(|(p, _)| p).collect();
+    assert_eq!(payloads, vec!["inline", "0123456789"]);
+  }
+
+  #[tokio::test]
+  async fn test_incomplete_set_is_not_acked() {
+    let chunks = chunks(b"0123456789").await;
+    let data = vec![(chunks[0].clone(), ack(0)), (chunks[1].clone(), ack(0))];
+    let sub = subscriber(data);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(messages.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_timeout() {
+    let chunks = chunks(b"0123456789").await;
+    let data = vec![
+      (chunks[0].clone(), ack(0)),
+      (Bytes::from_static(b"inline"), ack(0)),
+    ];
+    let sub = subscriber(data).timeout(Duration::ZERO);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert_eq!(messages.len(), 2);
+    assert!(
+      messages[0]
+        .as_ref()
+        .is_err_and(|err| err.to_string().contains("timed out"))
+    );
+    assert!(messages[1].is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_timeout_without_further_message() {
+    let chunks = chunks(b"0123456789").await;
+    let quiet = Quiet(Mutex::new(vec![(chunks[0].clone(), ack(0))]));
+    let sub =
+      Subscriber::new(Arc::new(quiet)).timeout(Duration::from_millis(20));
+    let mut messages = sub.subscribe().await.unwrap();
+
+    let next = ::tokio::time::timeout(Duration::from_secs(5), messages.next())
+      .await
+      .expect("the transfer should time out without another message");
+
+    assert!(next.is_some_and(|msg| {
+      msg.is_err_and(|err| err.to_string().contains("timed out"))
+    }));
+  }
+
+  #[tokio::test]
+  async fn test_huge_total_is_rejected() {
+    let data = vec![(forged(u32::MAX), ack(0))];
+    let sub = subscriber(data);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(matches!(
+      &messages[..],
+      [Err(err)] if err.to_string().contains("at most 4096 allowed")
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_payload_too_large() {
+    let chunks = chunks(b"0123456789").await;
+    let data = chunks.into_iter().map(|c| (c, ack(0))).collect();
+    let sub = subscriber(data).max_payload(6);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(matches!(
+      &messages[..],
+      [Err(err)] if err.to_string().contains("maximum payload size")
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_too_many_transfers() {
+    let data = vec![(forged(2), ack(0)), (forged(2), ack(0))];
+    let sub = subscriber(data).max_transfers(1);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(matches!(
+      &messages[..],
+      [Err(err)] if err.to_string().starts_with("Too many transfers")
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_checksum_mismatch() {
+    let chunks = chunks(b"01234567").await;
+    let mut corrupted = Chunk::decode(&chunks[1]).unwrap().unwrap();
+    corrupted.data = Bytes::from_static(b"xxxx");
+    let data = vec![(chunks[0].clone(), ack(0)), (corrupted.encode(), ack(0))];
+    let sub = subscriber(data);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(
+      matches!(&messages[..], [Err(err)] if err.to_string().starts_with("Checksum mismatch"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_checksum_mismatch_acks_chunks() {
+    let chunks = chunks(b"01234567").await;
+    let mut corrupted = Chunk::decode(&chunks[1]).unwrap().unwrap();
+    corrupted.data = Bytes::from_static(b"xxxx");
+    let data = vec![(chunks[0].clone(), ack(1)), (corrupted.encode(), ack(1))];
+    let sub = subscriber(data).ack_rejected(true);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(
+      matches!(&messages[..], [Err(err)] if err.to_string().starts_with("Checksum mismatch"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_malformed_chunk_is_acked() {
+    let chunks = chunks(b"01234567").await;
+    let truncated = chunks[0].slice(..chunks[0].len() - 12);
+    let data = vec![(truncated, ack(1))];
+    let sub = subscriber(data).ack_rejected(true);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(matches!(
+      &messages[..],
+      [Err(err)] if err.to_string() == "Malformed chunk header"
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_timeout_acks_chunks() {
+    let chunks = chunks(b"0123456789").await;
+    let quiet = Quiet(Mutex::new(vec![(chunks[0].clone(), ack(1))]));
+    let sub = Subscriber::new(Arc::new(quiet))
+      .timeout(Duration::from_millis(20))
+      .ack_rejected(true);
+    let mut messages = sub.subscribe().await.unwrap();
+
+    let next = ::tokio::time::timeout(Duration::from_secs(5), messages.next())
+      .await
+      .expect("the transfer should time out without another message");
+
+    assert!(next.is_some_and(|msg| {
+      msg.is_err_and(|err| err.to_string().contains("timed out"))
+    }));
+  }
+}
@@ -0,0 +1,61 @@
+//! Claim-check wrappers for oversized payloads.
+//!
+//! NATS rejects messages above its maximum payload size, and large entries in
+//! Redis streams stay in memory until the stream is trimmed. The claim-check
+//! pattern keeps such payloads out of the broker: the [`Publisher`] stores
+//! every payload above a threshold in a [`BlobStore`] and publishes a small
+//! reference instead, and the [`Subscriber`] transparently fetches the
+//! referenced blob and deletes it once the message is acknowledged.
+//!
+//! Both wrappers implement the broker traits, so they can be used with
+//! [`Pub`](crate::Pub) and [`Sub`](crate::Sub) like any other broker.
+//!
+//! # Blob Stores
+//!
+//! - [`FsBlobStore`]: Files in a local (or shared) directory.
+//! - [`RedisBlobStore`]: Redis keys with a TTL (requires the `redis` feature).
+//! - NATS JetStream [`ObjectStore`](async_nats::jetstream::object_store::ObjectStore)
+//!   (requires the `nats` feature).
+//!
+//! Other stores can be plugged in by implementing [`BlobStore`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use object_transfer::brokers::{PubBrokerTrait, SubBrokerTrait};
+//! use object_transfer::brokers::claim_check::{
+//!   FsBlobStore, Publisher, Subscriber,
+//! };
+//!
+//! fn wrap(
+//!     pub_broker: Arc<dyn PubBrokerTrait + Send + Sync>,
+//!     sub_broker: Arc<dyn SubBrokerTrait + Send + Sync>,
+//! ) -> (Publisher, Subscriber) {
+//!     let store = Arc::new(FsBlobStore::new("/var/spool/object-transfer"));
+//!     (
+//!         Publisher::new(pub_broker, store.clone()).threshold(64 * 1024),
+//!         Subscriber::new(sub_broker, store),
+//!     )
+//! }
+//! ```
+
+mod ack;
+mod errors;
+mod fs;
+#[cfg(feature = "nats")]
+mod nats;
+mod publisher;
+#[cfg(feature = "redis")]
+mod redis;
+mod reference;
+mod store;
+mod subscriber;
+
+pub use self::errors::ClaimCheckError;
+pub use self::fs::FsBlobStore;
+pub use self::publisher::{DEFAULT_THRESHOLD, Publisher};
+#[cfg(feature = "redis")]
+pub use self::redis::RedisBlobStore;
+pub use self::store::BlobStore;
+pub use self::subscriber::Subscriber;
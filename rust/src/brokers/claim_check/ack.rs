@@ -0,0 +1,38 @@
+use ::std::sync::Arc;
+
+use ::async_trait::async_trait;
+
+use crate::errors::AckError;
+use crate::traits::AckTrait;
+
+use super::store::BlobStore;
+
+/// Acknowledgment deleting the referenced blob after the message is acked.
+pub(super) struct Ack {
+  inner: Arc<dyn AckTrait + Send + Sync>,
+  store: Arc<dyn BlobStore + Send + Sync>,
+  key: String,
+}
+
+impl Ack {
+  pub(super) fn new(
+    inner: Arc<dyn AckTrait + Send + Sync>,
+    store: Arc<dyn BlobStore + Send + Sync>,
+    key: String,
+  ) -> Self {
+    Self { inner, store, key }
+  }
+}
+
+#[async_trait]
+impl AckTrait for Ack {
+  /// Acknowledges the message, then deletes the referenced blob.
+  ///
+  /// The blob is kept if the acknowledgment fails, so that the redelivered
+  /// message can still be resolved.
+  async fn ack(&self) -> Result<(), AckError> {
+    self.inner.ack().await?;
+    self.store.delete(&self.key).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,20 @@
+use ::thiserror::Error;
+
+use crate::errors::BrokerError;
+
+/// Errors that can occur while resolving claim-check references.
+#[derive(Error, Debug)]
+pub enum ClaimCheckError {
+  /// The message carries the reference marker, but no valid blob key.
+  #[error("Invalid claim-check reference")]
+  InvalidReference,
+  /// The referenced blob doesn't exist (anymore) in the blob store.
+  #[error("Blob not found: {0}")]
+  NotFound(String),
+}
+
+impl From<ClaimCheckError> for BrokerError {
+  fn from(err: ClaimCheckError) -> Self {
+    BrokerError::new(err)
+  }
+}
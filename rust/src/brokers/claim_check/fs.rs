@@ -0,0 +1,83 @@
+use ::std::io::ErrorKind;
+use ::std::path::PathBuf;
+
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::tokio::fs;
+
+use crate::errors::BrokerError;
+
+use super::errors::ClaimCheckError;
+use super::store::BlobStore;
+
+/// Blob store keeping each blob in a file of a directory.
+///
+/// The directory is created on the first write. Publishers and subscribers
+/// running on different hosts need to share the directory (e.g. over NFS).
+#[derive(Clone, Debug)]
+pub struct FsBlobStore {
+  dir: PathBuf,
+}
+
+impl FsBlobStore {
+  /// Creates a new filesystem blob store.
+  ///
+  /// # Parameters
+  /// - `dir`: Directory holding the blobs.
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    Self { dir: dir.into() }
+  }
+}
+
+#[async_trait]
+impl BlobStore for FsBlobStore {
+  async fn put(&self, key: &str, data: Bytes) -> Result<(), BrokerError> {
+    fs::create_dir_all(&self.dir)
+      .await
+      .map_err(BrokerError::new)?;
+    fs::write(self.dir.join(key), data)
+      .await
+      .map_err(BrokerError::new)
+  }
+
+  async fn get(&self, key: &str) -> Result<Bytes, BrokerError> {
+    match fs::read(self.dir.join(key)).await {
+      Ok(data) => Ok(Bytes::from(data)),
+      Err(err) if err.kind() == ErrorKind::NotFound => {
+        Err(ClaimCheckError::NotFound(key.to_string()).into())
+      }
+      Err(err) => Err(BrokerError::new(err)),
+    }
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), BrokerError> {
+    match fs::remove_file(self.dir.join(key)).await {
+      Err(err) if err.kind() != ErrorKind::NotFound => {
+        Err(BrokerError::new(err))
+      }
+      _ => Ok(()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::super::reference;
+  use super::*;
+
+  #[tokio::test]
+  async fn test_put_get_delete() {
+    let dir = ::std::env::temp_dir().join(reference::new_key());
+    let store = FsBlobStore::new(&dir);
+    let key = reference::new_key();
+
+    store.put(&key, Bytes::from_static(b"blob")).await.unwrap();
+    let data = store.get(&key).await.unwrap();
+    store.delete(&key).await.unwrap();
+    let missing = store.get(&key).await;
+    ::std::fs::remove_dir(&dir).unwrap();
+
+    assert_eq!(data, Bytes::from_static(b"blob"));
+    assert!(missing.is_err());
+  }
+}
@@ -0,0 +1,38 @@
+use ::async_nats::jetstream::object_store::{GetErrorKind, ObjectStore};
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::tokio::io::AsyncReadExt;
+
+use crate::errors::BrokerError;
+
+use super::errors::ClaimCheckError;
+use super::store::BlobStore;
+
+#[async_trait]
+impl BlobStore for ObjectStore {
+  async fn put(&self, key: &str, data: Bytes) -> Result<(), BrokerError> {
+    ObjectStore::put(self, key, &mut data.as_ref()).await?;
+    Ok(())
+  }
+
+  async fn get(&self, key: &str) -> Result<Bytes, BrokerError> {
+    let mut object = match ObjectStore::get(self, key).await {
+      Ok(object) => object,
+      Err(err) if err.kind() == GetErrorKind::NotFound => {
+        return Err(ClaimCheckError::NotFound(key.to_string()).into());
+      }
+      Err(err) => return Err(err.into()),
+    };
+    let mut data = Vec::new();
+    object
+      .read_to_end(&mut data)
+      .await
+      .map_err(BrokerError::new)?;
+    Ok(Bytes::from(data))
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), BrokerError> {
+    ObjectStore::delete(self, key).await?;
+    Ok(())
+  }
+}
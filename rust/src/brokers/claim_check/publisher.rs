@@ -0,0 +1,166 @@
+use ::std::sync::Arc;
+
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+
+use super::super::traits::PubBrokerTrait;
+use crate::errors::BrokerError;
+
+use super::reference;
+use super::store::BlobStore;
+
+/// Default size (in bytes) above which payloads are published by reference.
+///
+/// Half of the default maximum payload of NATS (1 MiB).
+pub const DEFAULT_THRESHOLD: usize = 512 * 1024;
+
+/// Publisher storing oversized payloads in a blob store.
+///
+/// Payloads larger than the threshold are stored in the [`BlobStore`] and
+/// replaced by a reference to the stored blob; smaller payloads are
+/// published as-is.
+#[derive(Clone)]
+pub struct Publisher {
+  inner: Arc<dyn PubBrokerTrait + Send + Sync>,
+  store: Arc<dyn BlobStore + Send + Sync>,
+  threshold: usize,
+}
+
+impl Publisher {
+  /// Creates a new claim-check publisher with [`DEFAULT_THRESHOLD`].
+  ///
+  /// # Parameters
+  /// - `inner`: Broker publishing the payloads and references.
+  /// - `store`: Blob store holding the oversized payloads.
+  pub fn new(
+    inner: Arc<dyn PubBrokerTrait + Send + Sync>,
+    store: Arc<dyn BlobStore + Send + Sync>,
+  ) -> Self {
+    Self {
+      inner,
+      store,
+      threshold: DEFAULT_THRESHOLD,
+    }
+  }
+
+  /// Sets the size above which payloads are published by reference.
+  ///
+  /// # Parameters
+  /// - `threshold`: The payload size in bytes.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn threshold(mut self, threshold: usize) -> Self {
+    self.threshold = threshold;
+    self
+  }
+}
+
+#[async_trait]
+impl PubBrokerTrait for Publisher {
+  /// Publishes `payload`, or a reference to it if it exceeds the threshold.
+  ///
+  /// If publishing the reference fails, the stored blob is deleted again.
+  async fn publish(
+    &self,
+    topic: &str,
+    payload: Bytes,
+  ) -> Result<(), BrokerError> {
+    if payload.len() <= self.threshold {
+      return self.inner.publish(topic, payload).await;
+    }
+    let key = reference::new_key();
+    self.store.put(&key, payload).await?;
+    if let Err(err) = self.inner.publish(topic, reference::encode(&key)).await
+    {
+      let _ = self.store.delete(&key).await;
+      return Err(err);
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::std::sync::Mutex;
+
+  use ::mockall::predicate::{always, eq};
+
+  use super::super::store::MockBlobStore;
+  use super::*;
+  use crate::brokers::traits::MockPubBrokerTrait;
+  use crate::tests::error::MockBrokerErr;
+
+  fn capture() -> (Arc<Mutex<Vec<Bytes>>>, MockPubBrokerTrait) {
+    let published = Arc::new(Mutex::new(Vec::new()));
+    let mut inner = MockPubBrokerTrait::new();
+    let sink = published.clone();
+    inner
+      .expect_publish()
+      .with(eq("topic"), always())
+      .returning(move |_, payload| {
+        sink.lock().unwrap().push(payload);
+        Ok(())
+      });
+    (published, inner)
+  }
+
+  #[tokio::test]
+  async fn test_small_payload_is_inlined() {
+    let (published, inner) = capture();
+    let mut store = MockBlobStore::new();
+    store.expect_put().never();
+    let publisher =
+      Publisher::new(Arc::new(inner), Arc::new(store)).threshold(4);
+
+    publisher
+      .publish("topic", Bytes::from_static(b"1234"))
+      .await
+      .unwrap();
+
+    assert_eq!(
+      *published.lock().unwrap(),
+      vec![Bytes::from_static(b"1234")]
+    );
+  }
+
+  #[tokio::test]
+  async fn test_large_payload_is_referenced() {
+    let (published, inner) = capture();
+    let stored = Arc::new(Mutex::new(None));
+    let mut store = MockBlobStore::new();
+    let sink = stored.clone();
+    store.expect_put().times(1).returning(move |key, data| {
+      *sink.lock().unwrap() = Some((key.to_string(), data));
+      Ok(())
+    });
+    let publisher =
+      Publisher::new(Arc::new(inner), Arc::new(store)).threshold(4);
+
+    publisher
+      .publish("topic", Bytes::from_static(b"12345"))
+      .await
+      .unwrap();
+
+    let (key, data) = stored.lock().unwrap().take().unwrap();
+    assert_eq!(data, Bytes::from_static(b"12345"));
+    assert_eq!(*published.lock().unwrap(), vec![reference::encode(&key)]);
+  }
+
+  #[tokio::test]
+  async fn test_blob_is_deleted_on_publish_failure() {
+    let mut inner = MockPubBrokerTrait::new();
+    inner
+      .expect_publish()
+      .returning(|_, _| Err(BrokerError::new(MockBrokerErr)));
+    let mut store = MockBlobStore::new();
+    store.expect_put().times(1).returning(|_, _| Ok(()));
+    store.expect_delete().times(1).returning(|_| Ok(()));
+    let publisher =
+      Publisher::new(Arc::new(inner), Arc::new(store)).threshold(0);
+
+    let result = publisher.publish("topic", Bytes::from_static(b"1")).await;
+
+    assert!(result.is_err());
+  }
+}
@@ -0,0 +1,91 @@
+use ::std::time::Duration;
+
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::redis::AsyncCommands;
+use ::redis::aio::MultiplexedConnection;
+
+use crate::errors::BrokerError;
+
+use super::errors::ClaimCheckError;
+use super::store::BlobStore;
+
+/// Blob store keeping each blob in a Redis key with a TTL.
+///
+/// The TTL bounds the lifetime of blobs whose message is never
+/// acknowledged, so it should exceed the time the messages may stay
+/// unprocessed in the stream.
+#[derive(Clone)]
+pub struct RedisBlobStore {
+  con: MultiplexedConnection,
+  ttl: Duration,
+  prefix: String,
+}
+
+impl RedisBlobStore {
+  /// Creates a new Redis blob store.
+  ///
+  /// Keys are prefixed with `claim-check:` by default.
+  ///
+  /// # Arguments
+  ///
+  /// * `con` - A reference to a multiplexed Redis connection
+  /// * `ttl` - Time to live of the blobs (rounded down to seconds)
+  pub fn new(con: &MultiplexedConnection, ttl: Duration) -> Self {
+    Self {
+      con: con.clone(),
+      ttl,
+      prefix: "claim-check:".to_string(),
+    }
+  }
+
+  /// Sets the prefix of the Redis keys holding the blobs.
+  ///
+  /// # Parameters
+  /// - `prefix`: The key prefix.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+    self.prefix = prefix.into();
+    self
+  }
+
+  fn redis_key(&self, key: &str) -> String {
+    format!("{}{}", self.prefix, key)
+  }
+}
+
+#[async_trait]
+impl BlobStore for RedisBlobStore {
+  async fn put(&self, key: &str, data: Bytes) -> Result<(), BrokerError> {
+    let mut con = self.con.clone();
+    con
+      .set_ex::<_, _, ()>(
+        self.redis_key(key),
+        data.to_vec(),
+        self.ttl.as_secs().max(1),
+      )
+      .await
+      .map_err(BrokerError::new)
+  }
+
+  async fn get(&self, key: &str) -> Result<Bytes, BrokerError> {
+    let mut con = self.con.clone();
+    let data: Option<Vec<u8>> = con
+      .get(self.redis_key(key))
+      .await
+      .map_err(BrokerError::new)?;
+    data
+      .map(Bytes::from)
+      .ok_or_else(|| ClaimCheckError::NotFound(key.to_string()).into())
+  }
+
+  async fn delete(&self, key: &str) -> Result<(), BrokerError> {
+    let mut con = self.con.clone();
+    con
+      .del::<_, ()>(self.redis_key(key))
+      .await
+      .map_err(BrokerError::new)
+  }
+}
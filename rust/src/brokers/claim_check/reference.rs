@@ -0,0 +1,70 @@
+//! Wire format of the references published in place of stored payloads.
+//!
+//! A reference is the marker below followed by the blob key, a hyphenated
+//! UUID. The marker starts with `0xC1`, which is neither valid UTF-8 nor a
+//! valid MessagePack byte, so it doesn't collide with regular payloads.
+
+use ::bytes::{BufMut, Bytes, BytesMut};
+use ::uuid::Uuid;
+
+use super::errors::ClaimCheckError;
+
+/// Marker prefixing every reference.
+const MARKER: &[u8] = b"\xC1\xC1object-transfer/claim-check:";
+
+/// Generates a new blob key.
+pub(super) fn new_key() -> String {
+  Uuid::new_v4().to_string()
+}
+
+/// Builds the reference to the blob stored under `key`.
+pub(super) fn encode(key: &str) -> Bytes {
+  let mut buf = BytesMut::with_capacity(MARKER.len() + key.len());
+  buf.put_slice(MARKER);
+  buf.put_slice(key.as_bytes());
+  buf.freeze()
+}
+
+/// Extracts the blob key from `payload`.
+///
+/// # Returns
+/// `None` if `payload` isn't a reference. Keys are validated so that a
+/// forged reference can't address arbitrary entries of the blob store.
+pub(super) fn decode(
+  payload: &[u8],
+) -> Option<Result<String, ClaimCheckError>> {
+  let key = payload.strip_prefix(MARKER)?;
+  Some(
+    str::from_utf8(key)
+      .ok()
+      .and_then(|key| Uuid::try_parse(key).ok())
+      .map(|key| key.to_string())
+      .ok_or(ClaimCheckError::InvalidReference),
+  )
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_round_trip() {
+    let key = new_key();
+
+    let decoded = decode(&encode(&key)).unwrap().unwrap();
+
+    assert_eq!(decoded, key);
+  }
+
+  #[test]
+  fn test_regular_payload() {
+    assert!(decode(b"{\"id\": 1}").is_none());
+  }
+
+  #[test]
+  fn test_forged_key() {
+    let result = decode(&encode("../../etc/passwd")).unwrap();
+
+    assert!(matches!(result, Err(ClaimCheckError::InvalidReference)));
+  }
+}
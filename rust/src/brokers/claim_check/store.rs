@@ -0,0 +1,30 @@
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+#[cfg(test)]
+use ::mockall::automock;
+
+use crate::errors::BrokerError;
+
+/// Storage holding the payloads published by reference.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait BlobStore {
+  /// Stores `data` under `key`.
+  ///
+  /// # Parameters
+  /// - `key`: Unique key of the blob.
+  /// - `data`: The payload to store.
+  async fn put(&self, key: &str, data: Bytes) -> Result<(), BrokerError>;
+
+  /// Fetches the blob stored under `key`.
+  ///
+  /// # Parameters
+  /// - `key`: Key of the blob.
+  async fn get(&self, key: &str) -> Result<Bytes, BrokerError>;
+
+  /// Deletes the blob stored under `key`.
+  ///
+  /// # Parameters
+  /// - `key`: Key of the blob.
+  async fn delete(&self, key: &str) -> Result<(), BrokerError>;
+}
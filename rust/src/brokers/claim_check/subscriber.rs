@@ -0,0 +1,204 @@
+use ::std::sync::Arc;
+
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::futures::stream::{BoxStream, StreamExt};
+
+use super::super::traits::SubBrokerTrait;
+use crate::errors::BrokerError;
+use crate::traits::AckTrait;
+
+use super::ack::Ack;
+use super::errors::ClaimCheckError;
+use super::reference;
+use super::store::BlobStore;
+
+/// Subscriber resolving the references published by the claim-check
+/// [`Publisher`](super::Publisher).
+///
+/// Referenced payloads are fetched from the [`BlobStore`], and the blob is
+/// deleted once the message is acknowledged. Other messages are passed
+/// through unchanged.
+///
+/// A reference whose blob is missing, e.g. because its TTL expired or it was
+/// already deleted before a redelivery, can never be resolved: its message is
+/// acknowledged before [`ClaimCheckError::NotFound`] is yielded, so that the
+/// broker doesn't redeliver it forever.
+#[derive(Clone)]
+pub struct Subscriber {
+  inner: Arc<dyn SubBrokerTrait + Send + Sync>,
+  store: Arc<dyn BlobStore + Send + Sync>,
+}
+
+impl Subscriber {
+  /// Creates a new claim-check subscriber.
+  ///
+  /// # Parameters
+  /// - `inner`: Broker delivering the payloads and references.
+  /// - `store`: Blob store holding the oversized payloads.
+  pub fn new(
+    inner: Arc<dyn SubBrokerTrait + Send + Sync>,
+    store: Arc<dyn BlobStore + Send + Sync>,
+  ) -> Self {
+    Self { inner, store }
+  }
+}
+
+#[async_trait]
+impl SubBrokerTrait for Subscriber {
+  async fn subscribe(
+    &self,
+  ) -> Result<
+    BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+    BrokerError,
+  > {
+    let messages = self.inner.subscribe().await?;
+    let store = self.store.clone();
+    let resolved = messages.then(move |msg| {
+      let store = store.clone();
+      async move {
+        let (payload, ack) = msg?;
+        let Some(key) = reference::decode(&payload) else {
+          return Ok((payload, ack));
+        };
+        let key = key?;
+        let blob = match store.get(&key).await {
+          Ok(blob) => blob,
+          Err(err) => {
+            if let Some(ClaimCheckError::NotFound(_)) = err.downcast_ref() {
+              ack.ack().await.map_err(BrokerError::new)?;
+            }
+            return Err(err);
+          }
+        };
+        let ack: Arc<dyn AckTrait + Send + Sync> =
+          Arc::new(Ack::new(ack, store, key));
+        Ok((blob, ack))
+      }
+    });
+    Ok(resolved.boxed())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::futures::stream::TryStreamExt;
+  use ::mockall::predicate::eq;
+
+  use super::super::store::MockBlobStore;
+  use super::*;
+  use crate::errors::AckError;
+  use crate::tests::error::MockBrokerErr;
+  use crate::tests::subscribe::SubscribeMock;
+  use crate::traits::MockAckTrait;
+
+  fn ack(result: fn() -> Result<(), AckError>) -> Arc<MockAckTrait> {
+    let mut ack = MockAckTrait::new();
+    ack.expect_ack().times(1).returning(result);
+    Arc::new(ack)
+  }
+
+  fn subscriber(
+    data: Vec<(Bytes, Arc<dyn AckTrait + Send + Sync>)>,
+    store: MockBlobStore,
+  ) -> Subscriber {
+    Subscriber::new(Arc::new(SubscribeMock::new(data)), Arc::new(store))
+  }
+
+  #[tokio::test]
+  async fn test_inline_payload_is_passed_through() {
+    let mut store = MockBlobStore::new();
+    store.expect_get().never();
+    store.expect_delete().never();
+    let sub =
+      subscriber(vec![(Bytes::from_static(b"inline"), ack(|| Ok(())))], store);
+
+    let messages: Vec<_> =
+      sub.subscribe().await.unwrap().try_collect().await.unwrap();
+    let (payload, ack) = &messages[0];
+    ack.ack().await.unwrap();
+
+    assert_eq!(payload, &Bytes::from_static(b"inline"));
+  }
+
+  #[tokio::test]
+  async fn test_reference_is_resolved_and_deleted_after_ack() {
+    let key = reference::new_key();
+    let mut store = MockBlobStore::new();
+    store
+      .expect_get()
+      .with(eq(key.clone()))
+      .times(1)
+      .returning(|_| Ok(Bytes::from_static(b"blob")));
+    store
+      .expect_delete()
+      .with(eq(key.clone()))
+      .times(1)
+      .returning(|_| Ok(()));
+    let sub =
+      subscriber(vec![(reference::encode(&key), ack(|| Ok(())))], store);
+
+    let messages: Vec<_> =
+      sub.subscribe().await.unwrap().try_collect().await.unwrap();
+    let (payload, ack) = &messages[0];
+    ack.ack().await.unwrap();
+
+    assert_eq!(payload, &Bytes::from_static(b"blob"));
+  }
+
+  #[tokio::test]
+  async fn test_blob_is_kept_on_ack_failure() {
+    let key = reference::new_key();
+    let mut store = MockBlobStore::new();
+    store
+      .expect_get()
+      .returning(|_| Ok(Bytes::from_static(b"blob")));
+    store.expect_delete().never();
+    let sub = subscriber(
+      vec![(reference::encode(&key), ack(|| Err(AckError::ErrorTest)))],
+      store,
+    );
+
+    let messages: Vec<_> =
+      sub.subscribe().await.unwrap().try_collect().await.unwrap();
+    let (_, ack) = &messages[0];
+
+    assert!(ack.ack().await.is_err());
+  }
+
+  #[tokio::test]
+  async fn test_missing_blob_is_acked() {
+    let key = reference::new_key();
+    let mut store = MockBlobStore::new();
+    store
+      .expect_get()
+      .returning(|key| Err(ClaimCheckError::NotFound(key.to_string()).into()));
+    store.expect_delete().never();
+    let sub =
+      subscriber(vec![(reference::encode(&key), ack(|| Ok(())))], store);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(matches!(
+      &messages[..],
+      [Err(err)] if err.to_string().starts_with("Blob not found")
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_blob_fetch_failure() {
+    let key = reference::new_key();
+    let mut store = MockBlobStore::new();
+    store
+      .expect_get()
+      .returning(|_| Err(BrokerError::new(MockBrokerErr)));
+    let mut unacked = MockAckTrait::new();
+    unacked.expect_ack().never();
+    let sub =
+      subscriber(vec![(reference::encode(&key), Arc::new(unacked))], store);
+
+    let messages: Vec<_> = sub.subscribe().await.unwrap().collect().await;
+
+    assert!(matches!(&messages[..], [Err(_)]));
+  }
+}
@@ -16,4 +16,12 @@ impl BrokerError {
   {
     Self(Box::new(err))
   }
+
+  /// Returns the wrapped error if it is of type `E`.
+  pub fn downcast_ref<E>(&self) -> Option<&E>
+  where
+    E: StdError + 'static,
+  {
+    self.0.downcast_ref()
+  }
 }
@@ -2,15 +2,53 @@
 //!
 //! This module provides a NATS-based connector,
 //! enabling asynchronous message publishing and subscription management.
+//!
+//! Publishing is available through the JetStream [`Context`] and, for
+//! ephemeral data, through the NATS core [`Client`]. Messages are received
+//! from JetStream with [`SubFetcher`] (pull consumers) or [`PushSubscriber`]
+//! (push consumers), or from NATS core with [`CoreSubscriber`]. Streams and
+//! consumers can be managed declaratively with [`Admin`].
+//!
+//! KV buckets can be used as well: values are put by publishing to a
+//! [`Store`], and changes are watched with [`KvWatcher`] and decoded with
+//! [`KvDecoder`]. Large objects are stored in object store buckets with
+//! [`TypedObjectStore`].
+//!
+//! The state of a connection is observed with [`ConnectionEvents`], and
+//! [`Resubscriber`] keeps the streams of a subscriber alive across
+//! reconnects.
+//!
+//! [`Context`]: async_nats::jetstream::Context
+//! [`Client`]: async_nats::Client
+//! [`Store`]: async_nats::jetstream::kv::Store
 
+mod admin;
+mod core_sub;
 mod errors;
+mod events;
 pub mod impl_ack;
+pub mod impl_client;
 pub mod impl_ctx;
+pub mod impl_kv;
+mod kv;
+mod object_store;
 pub mod options;
+mod push_sub;
+mod resub;
 mod sub_fetcher;
+mod subscriptions;
 #[cfg(test)]
 mod tests;
 
-pub use errors::NatsSubFetcherError;
-pub use options::SubFetcherOpt;
+pub use admin::{
+  Admin, COMPARED_FIELDS, ConfigDrift, ConsumerStatus, StreamStatus,
+};
+pub use core_sub::CoreSubscriber;
+pub use errors::{NatsAdminError, NatsObjectStoreError, NatsSubFetcherError};
+pub use events::{ConnectionEvent, ConnectionEvents};
+pub use kv::{KvChange, KvDecoder, KvError, KvOperation, KvWatcher};
+pub use object_store::{ObjectChange, TypedObjectStore};
+pub use options::{BatchOpt, PushSubscriberOpt, SubFetcherOpt, UnSubMode};
+pub use push_sub::PushSubscriber;
+pub use resub::Resubscriber;
 pub use sub_fetcher::SubFetcher;
@@ -0,0 +1,614 @@
+use ::std::fmt::{Display, Formatter, Result as FmtResult};
+use ::std::sync::Arc;
+
+use ::async_nats::jetstream::ErrorCode;
+use ::async_nats::jetstream::consumer::{
+  FromConsumer, Info as ConsumerInfo, IntoConsumerConfig,
+};
+use ::async_nats::jetstream::context::{
+  ConsumerInfoErrorKind, Context, GetStreamErrorKind,
+};
+use ::async_nats::jetstream::stream::{
+  Config as StreamConfig, Info as StreamInfo, Stream as JStream,
+};
+use ::serde::Serialize;
+use ::serde_json::{Map, Value};
+
+use crate::errors::BrokerError;
+
+use super::errors::NatsAdminError;
+
+/// A field of a stream or consumer configuration that differs between the
+/// desired configuration and the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDrift {
+  /// The name of the field, as in the JetStream API.
+  pub field: String,
+  /// The desired value.
+  pub desired: Value,
+  /// The value on the server.
+  pub actual: Value,
+}
+
+impl Display for ConfigDrift {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    write!(
+      f,
+      "{}: desired {}, actual {}",
+      self.field, self.desired, self.actual
+    )
+  }
+}
+
+/// Summary of the state of a stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamStatus {
+  /// The number of messages in the stream.
+  pub messages: u64,
+  /// The total size of the messages in bytes.
+  pub bytes: u64,
+  /// The sequence number of the first message.
+  pub first_sequence: u64,
+  /// The sequence number of the last message.
+  pub last_sequence: u64,
+  /// The number of consumers of the stream.
+  pub consumer_count: usize,
+}
+
+impl From<&StreamInfo> for StreamStatus {
+  fn from(info: &StreamInfo) -> Self {
+    Self {
+      messages: info.state.messages,
+      bytes: info.state.bytes,
+      first_sequence: info.state.first_sequence,
+      last_sequence: info.state.last_sequence,
+      consumer_count: info.state.consumer_count,
+    }
+  }
+}
+
+/// Summary of the state of a consumer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerStatus {
+  /// The number of messages not delivered yet.
+  pub pending: u64,
+  /// The number of messages delivered but not acknowledged yet.
+  pub ack_pending: usize,
+  /// The number of messages delivered more than once.
+  pub redelivered: usize,
+  /// The stream sequence of the last delivered message.
+  pub delivered: u64,
+  /// The stream sequence below which all messages are acknowledged.
+  pub ack_floor: u64,
+}
+
+impl From<&ConsumerInfo> for ConsumerStatus {
+  fn from(info: &ConsumerInfo) -> Self {
+    Self {
+      pending: info.num_pending,
+      ack_pending: info.num_ack_pending,
+      redelivered: info.num_redelivered,
+      delivered: info.delivered.stream_sequence,
+      ack_floor: info.ack_floor.stream_sequence,
+    }
+  }
+}
+
+/// Fields compared even when left to their default value, since the server
+/// keeps their default as-is, up to normalization of unlimited limits.
+pub const COMPARED_FIELDS: &[&str] = &[
+  "storage",
+  "retention",
+  "discard",
+  "max_msgs",
+  "max_bytes",
+  "max_age",
+  "max_msgs_per_subject",
+  "max_consumers",
+  "max_msg_size",
+  "deliver_policy",
+  "ack_policy",
+  "replay_policy",
+  "max_deliver",
+];
+
+/// Limits for which the server reports `0` (unlimited) as `-1`.
+const UNLIMITED_FIELDS: &[&str] = &[
+  "max_msgs",
+  "max_bytes",
+  "max_msgs_per_subject",
+  "max_consumers",
+  "max_msg_size",
+  "max_deliver",
+];
+
+/// Rewrites a field value the way the server stores it.
+fn normalize(field: &str, value: Value) -> Value {
+  match value.as_i64() {
+    Some(0) if UNLIMITED_FIELDS.contains(&field) => Value::from(-1),
+    Some(0) if field == "num_replicas" => Value::from(1),
+    _ => value,
+  }
+}
+
+/// Compares the fields set in `desired` and the `compared` fields with
+/// `actual`.
+///
+/// Other fields of `desired` left to their default value are ignored, since
+/// the server fills them with its own defaults.
+fn diff<T: Serialize + Default>(
+  desired: &T,
+  actual: &T,
+  compared: &[String],
+) -> Result<Vec<ConfigDrift>, NatsAdminError> {
+  let to_map = |value: &T| -> Result<Map<String, Value>, NatsAdminError> {
+    match ::serde_json::to_value(value)? {
+      Value::Object(map) => Ok(map),
+      _ => Ok(Map::new()),
+    }
+  };
+  let default = to_map(&T::default())?;
+  let actual = to_map(actual)?;
+  let mut desired = to_map(desired)?;
+  // Fields at their default value may be skipped when serialized.
+  for field in compared {
+    if !desired.contains_key(field) && actual.contains_key(field) {
+      let value = default.get(field).cloned().unwrap_or(Value::Null);
+      desired.insert(field.clone(), value);
+    }
+  }
+  let drifts = desired
+    .into_iter()
+    .filter(|(field, value)| {
+      compared.contains(field) || default.get(field) != Some(value)
+    })
+    .filter_map(|(field, desired)| {
+      let desired = normalize(&field, desired);
+      let actual =
+        normalize(&field, actual.get(&field).cloned().unwrap_or(Value::Null));
+      (actual != desired).then_some(ConfigDrift {
+        field,
+        desired,
+        actual,
+      })
+    })
+    .collect();
+  Ok(drifts)
+}
+
+/// Declarative administration of JetStream streams and consumers.
+///
+/// Unlike [`SubFetcher::new`](super::SubFetcher::new), which reuses an
+/// existing stream as-is, the methods of this struct compare the desired
+/// configuration with the server, so that configuration drift is either
+/// reported or applied.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use async_nats::jetstream::stream::Config as StreamConfig;
+/// use object_transfer::brokers::nats::Admin;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let client = async_nats::connect("demo.nats.io").await?;
+///   let admin = Admin::new(Arc::new(async_nats::jetstream::new(client)));
+///
+///   let status = admin
+///     .ensure_stream(&StreamConfig {
+///       name: "events".to_string(),
+///       subjects: vec!["events.>".to_string()],
+///       max_messages: 10_000,
+///       ..Default::default()
+///     })
+///     .await?;
+///   println!("{} messages", status.messages);
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Admin {
+  ctx: Arc<Context>,
+  compared: Vec<String>,
+}
+
+impl Admin {
+  /// Creates a new administration handle comparing the fields set in the
+  /// desired configurations and the [`COMPARED_FIELDS`].
+  ///
+  /// # Parameters
+  /// - `ctx`: JetStream context used to manage streams and consumers.
+  pub fn new(ctx: Arc<Context>) -> Self {
+    Self {
+      ctx,
+      compared: COMPARED_FIELDS.iter().map(|f| f.to_string()).collect(),
+    }
+  }
+
+  /// Also compares the given fields when the desired configuration leaves
+  /// them to their default value, so that drift back to the default is
+  /// reported and applied.
+  ///
+  /// Only add fields whose default value the server keeps as-is; e.g. the
+  /// server replaces a zero `duplicate_window` with two minutes, which would
+  /// always be reported as drift.
+  ///
+  /// # Parameters
+  /// - `fields`: Field names, as in the JetStream API (e.g. `"description"`).
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn compare_fields<I, F>(mut self, fields: I) -> Self
+  where
+    I: IntoIterator<Item = F>,
+    F: Into<String>,
+  {
+    self.compared.extend(fields.into_iter().map(Into::into));
+    self
+  }
+
+  async fn stream(&self, name: &str) -> Result<JStream, NatsAdminError> {
+    self.ctx.get_stream(name).await.map_err(|e| match e.kind() {
+      GetStreamErrorKind::JetStream(err)
+        if err.error_code() == ErrorCode::STREAM_NOT_FOUND =>
+      {
+        NatsAdminError::StreamNotFound(name.to_string())
+      }
+      _ => BrokerError::from(e).into(),
+    })
+  }
+
+  async fn consumer_info(
+    &self,
+    stream: &JStream,
+    name: &str,
+  ) -> Result<ConsumerInfo, NatsAdminError> {
+    stream
+      .consumer_info(name)
+      .await
+      .map_err(|e| match e.kind() {
+        ConsumerInfoErrorKind::NotFound => {
+          NatsAdminError::ConsumerNotFound(name.to_string())
+        }
+        ConsumerInfoErrorKind::JetStream(err)
+          if err.error_code() == ErrorCode::CONSUMER_NOT_FOUND =>
+        {
+          NatsAdminError::ConsumerNotFound(name.to_string())
+        }
+        _ => BrokerError::from(e).into(),
+      })
+  }
+
+  /// Creates the stream, or updates it if its configuration drifted.
+  ///
+  /// # Parameters
+  /// - `cfg`: The desired stream configuration.
+  pub async fn ensure_stream(
+    &self,
+    cfg: &StreamConfig,
+  ) -> Result<StreamStatus, NatsAdminError> {
+    match self.diff_stream(cfg).await {
+      Ok(drifts) if drifts.is_empty() => self.stream_status(&cfg.name).await,
+      Ok(_) => {
+        let info = self
+          .ctx
+          .update_stream(cfg)
+          .await
+          .map_err(BrokerError::from)?;
+        Ok(StreamStatus::from(&info))
+      }
+      Err(NatsAdminError::StreamNotFound(_)) => {
+        let stream = self
+          .ctx
+          .create_stream(cfg.clone())
+          .await
+          .map_err(BrokerError::from)?;
+        Ok(StreamStatus::from(stream.cached_info()))
+      }
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Compares the desired stream configuration with the server.
+  ///
+  /// Only the fields set in `cfg` (i.e. differing from their default value)
+  /// and the compared fields (see [`Admin::compare_fields`]) are compared.
+  ///
+  /// # Parameters
+  /// - `cfg`: The desired stream configuration.
+  pub async fn diff_stream(
+    &self,
+    cfg: &StreamConfig,
+  ) -> Result<Vec<ConfigDrift>, NatsAdminError> {
+    let info = self
+      .stream(&cfg.name)
+      .await?
+      .get_info()
+      .await
+      .map_err(BrokerError::from)?;
+    diff(cfg, &info.config, &self.compared)
+  }
+
+  /// Checks that the stream exists and matches the desired configuration.
+  ///
+  /// # Parameters
+  /// - `cfg`: The desired stream configuration.
+  pub async fn validate_stream(
+    &self,
+    cfg: &StreamConfig,
+  ) -> Result<(), NatsAdminError> {
+    let drifts = self.diff_stream(cfg).await?;
+    if !drifts.is_empty() {
+      return Err(NatsAdminError::Drift(drifts));
+    }
+    Ok(())
+  }
+
+  /// Returns the state of a stream.
+  ///
+  /// # Parameters
+  /// - `stream`: The name of the stream.
+  pub async fn stream_status(
+    &self,
+    stream: &str,
+  ) -> Result<StreamStatus, NatsAdminError> {
+    let info = self
+      .stream(stream)
+      .await?
+      .get_info()
+      .await
+      .map_err(BrokerError::from)?;
+    Ok(StreamStatus::from(&info))
+  }
+
+  /// Deletes the messages of a subject from a stream.
+  ///
+  /// # Parameters
+  /// - `stream`: The name of the stream.
+  /// - `subject`: The subject whose messages are deleted. Wildcards are
+  ///   allowed.
+  ///
+  /// # Returns
+  /// The number of deleted messages
+  pub async fn purge_subject(
+    &self,
+    stream: &str,
+    subject: &str,
+  ) -> Result<u64, NatsAdminError> {
+    let response = self
+      .stream(stream)
+      .await?
+      .purge()
+      .filter(subject)
+      .await
+      .map_err(BrokerError::from)?;
+    Ok(response.purged)
+  }
+
+  /// Creates the consumer, or updates it if its configuration drifted.
+  ///
+  /// # Parameters
+  /// - `stream`: The name of the stream.
+  /// - `cfg`: The desired consumer configuration, e.g. a pull or push
+  ///   consumer configuration. It must have a name or a durable name.
+  pub async fn ensure_consumer<C>(
+    &self,
+    stream: &str,
+    cfg: &C,
+  ) -> Result<ConsumerStatus, NatsAdminError>
+  where
+    C: IntoConsumerConfig + FromConsumer + Default + Clone,
+  {
+    let js_stream = self.stream(stream).await?;
+    match self.diff_consumer(stream, cfg).await {
+      Ok(drifts) if drifts.is_empty() => {}
+      Ok(_) => {
+        js_stream
+          .update_consumer(cfg.clone())
+          .await
+          .map_err(BrokerError::from)?;
+      }
+      Err(NatsAdminError::ConsumerNotFound(_)) => {
+        js_stream
+          .create_consumer(cfg.clone())
+          .await
+          .map_err(BrokerError::from)?;
+      }
+      Err(e) => return Err(e),
+    }
+    let name = consumer_name(cfg)?;
+    self.consumer_status(stream, &name).await
+  }
+
+  /// Compares the desired consumer configuration with the server.
+  ///
+  /// Only the fields set in `cfg` (i.e. differing from their default value)
+  /// and the compared fields (see [`Admin::compare_fields`]) are compared.
+  ///
+  /// # Parameters
+  /// - `stream`: The name of the stream.
+  /// - `cfg`: The desired consumer configuration. It must have a name or a
+  ///   durable name.
+  pub async fn diff_consumer<C>(
+    &self,
+    stream: &str,
+    cfg: &C,
+  ) -> Result<Vec<ConfigDrift>, NatsAdminError>
+  where
+    C: IntoConsumerConfig + Default + Clone,
+  {
+    let name = consumer_name(cfg)?;
+    let stream = self.stream(stream).await?;
+    let info = self.consumer_info(&stream, &name).await?;
+    let desired = cfg.clone().into_consumer_config();
+    let drifts = diff(&desired, &info.config, &self.compared)?;
+    // Fields left to their default value in `C` may differ from the
+    // defaults of the merged consumer configuration.
+    let defaults =
+      ::serde_json::to_value(C::default().into_consumer_config())?;
+    Ok(
+      drifts
+        .into_iter()
+        .filter(|drift| {
+          self.compared.contains(&drift.field)
+            || defaults.get(&drift.field) != Some(&drift.desired)
+        })
+        .collect(),
+    )
+  }
+
+  /// Checks that the consumer exists and matches the desired configuration.
+  ///
+  /// # Parameters
+  /// - `stream`: The name of the stream.
+  /// - `cfg`: The desired consumer configuration. It must have a name or a
+  ///   durable name.
+  pub async fn validate_consumer<C>(
+    &self,
+    stream: &str,
+    cfg: &C,
+  ) -> Result<(), NatsAdminError>
+  where
+    C: IntoConsumerConfig + Default + Clone,
+  {
+    let drifts = self.diff_consumer(stream, cfg).await?;
+    if !drifts.is_empty() {
+      return Err(NatsAdminError::Drift(drifts));
+    }
+    Ok(())
+  }
+
+  /// Returns the state of a consumer.
+  ///
+  /// # Parameters
+  /// - `stream`: The name of the stream.
+  /// - `consumer`: The name of the consumer.
+  pub async fn consumer_status(
+    &self,
+    stream: &str,
+    consumer: &str,
+  ) -> Result<ConsumerStatus, NatsAdminError> {
+    let stream = self.stream(stream).await?;
+    let info = self.consumer_info(&stream, consumer).await?;
+    Ok(ConsumerStatus::from(&info))
+  }
+}
+
+/// Returns the name of the consumer, falling back to its durable name.
+fn consumer_name<C>(cfg: &C) -> Result<String, NatsAdminError>
+where
+  C: IntoConsumerConfig + Clone,
+{
+  let cfg = cfg.clone().into_consumer_config();
+  cfg
+    .name
+    .or(cfg.durable_name)
+    .ok_or(NatsAdminError::UnnamedConsumer)
+}
+
+#[cfg(test)]
+mod test {
+  use ::async_nats::jetstream::consumer::pull::Config as PullConfig;
+  use ::async_nats::jetstream::stream::StorageType;
+
+  use super::*;
+
+  #[test]
+  fn test_diff_ignores_unset_fields() {
+    let desired = StreamConfig {
+      name: "events".to_string(),
+      ..Default::default()
+    };
+    let actual = StreamConfig {
+      name: "events".to_string(),
+      max_messages: 100,
+      ..Default::default()
+    };
+
+    assert!(diff(&desired, &actual, &[]).unwrap().is_empty());
+  }
+
+  fn compared() -> Vec<String> {
+    COMPARED_FIELDS.iter().map(|f| f.to_string()).collect()
+  }
+
+  #[test]
+  fn test_diff_reports_drift_to_default() {
+    let desired = StreamConfig {
+      name: "events".to_string(),
+      ..Default::default()
+    };
+    let actual = StreamConfig {
+      name: "events".to_string(),
+      max_messages: 100,
+      storage: StorageType::Memory,
+      ..Default::default()
+    };
+
+    let drifts = diff(&desired, &actual, &compared()).unwrap();
+    let fields: Vec<_> = drifts.iter().map(|d| d.field.as_str()).collect();
+
+    assert_eq!(fields, vec!["max_msgs", "storage"]);
+    assert_eq!(drifts[0].desired, Value::from(-1));
+    assert_eq!(drifts[1].desired, Value::from("file"));
+  }
+
+  #[test]
+  fn test_diff_normalizes_unlimited() {
+    let desired = StreamConfig {
+      name: "events".to_string(),
+      ..Default::default()
+    };
+    let actual = StreamConfig {
+      name: "events".to_string(),
+      max_messages: -1,
+      max_bytes: -1,
+      max_consumers: -1,
+      ..Default::default()
+    };
+
+    assert!(diff(&desired, &actual, &compared()).unwrap().is_empty());
+  }
+
+  #[test]
+  fn test_diff_reports_drift() {
+    let desired = StreamConfig {
+      name: "events".to_string(),
+      max_messages: 100,
+      storage: StorageType::Memory,
+      ..Default::default()
+    };
+    let actual = StreamConfig {
+      name: "events".to_string(),
+      max_messages: 50,
+      storage: StorageType::Memory,
+      ..Default::default()
+    };
+
+    let drifts = diff(&desired, &actual, &compared()).unwrap();
+
+    assert_eq!(
+      drifts,
+      vec![ConfigDrift {
+        field: "max_msgs".to_string(),
+        desired: Value::from(100),
+        actual: Value::from(50),
+      }]
+    );
+  }
+
+  #[test]
+  fn test_consumer_name() {
+    let named = PullConfig {
+      durable_name: Some("projection".to_string()),
+      ..Default::default()
+    };
+
+    assert_eq!(consumer_name(&named).unwrap(), "projection");
+    assert!(matches!(
+      consumer_name(&PullConfig::default()),
+      Err(NatsAdminError::UnnamedConsumer)
+    ));
+  }
+}
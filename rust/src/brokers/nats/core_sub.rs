@@ -0,0 +1,141 @@
+use ::std::sync::Arc;
+
+use ::async_nats::Client;
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::futures::stream::BoxStream;
+use ::futures::{StreamExt, TryFutureExt};
+
+use crate::ack_noop::AckNoop;
+use crate::errors::{BrokerError, UnSubError};
+use crate::traits::{AckTrait, UnSubTrait};
+
+use super::super::traits::SubBrokerTrait;
+use super::subscriptions::Subscriptions;
+
+/// Subscribes to a subject with NATS core, without JetStream.
+///
+/// Messages aren't persisted: only the messages published while subscribed
+/// are received, and their acknowledgment handles are no-ops. This suits
+/// ephemeral, fire-and-forget data such as telemetry.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use serde::{Deserialize, Serialize};
+/// use futures::StreamExt;
+/// use object_transfer::{Pub, Sub, SubOpt, traits::{PubTrait, SubTrait}};
+/// use object_transfer::brokers::nats::CoreSubscriber;
+/// use object_transfer::encoders::{JSONDecoder, JSONEncoder};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Metric {
+///   value: f64,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let client = async_nats::connect("demo.nats.io").await?;
+///   let core = Arc::new(
+///     CoreSubscriber::new(client.clone(), "metrics").queue_group("workers"),
+///   );
+///   let sub: Sub<Metric, _> = Sub::new(
+///     core.clone(),
+///     core,
+///     Arc::new(JSONDecoder::new()),
+///     SubOpt::new(),
+///   );
+///   let publisher: Pub<Metric, _> =
+///     Pub::new(Arc::new(client), "metrics", Arc::new(JSONEncoder::new()));
+///
+///   let mut metrics = sub.subscribe().await?;
+///   publisher.publish(&Metric { value: 1.0 }).await?;
+///   if let Some(Ok((metric, _ack))) = metrics.next().await {
+///     println!("{}", metric.value);
+///   }
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CoreSubscriber {
+  client: Client,
+  subject: String,
+  queue_group: Option<String>,
+  subscriptions: Subscriptions,
+}
+
+impl CoreSubscriber {
+  /// Creates a subscriber of the given subject.
+  ///
+  /// # Parameters
+  /// - `client`: NATS client used to subscribe.
+  /// - `subject`: Subject to subscribe to. Wildcards are allowed.
+  pub fn new(client: Client, subject: impl Into<String>) -> Self {
+    Self {
+      client,
+      subject: subject.into(),
+      queue_group: None,
+      subscriptions: Subscriptions::default(),
+    }
+  }
+
+  /// Joins a queue group, so that each message is delivered to only one of
+  /// the subscribers of the group.
+  ///
+  /// # Parameters
+  /// - `queue_group`: The name of the queue group.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn queue_group(mut self, queue_group: impl Into<String>) -> Self {
+    self.queue_group = Some(queue_group.into());
+    self
+  }
+}
+
+#[async_trait]
+impl SubBrokerTrait for CoreSubscriber {
+  /// Subscribes to the subject, yielding the payloads of the received
+  /// messages along with no-op acknowledgment handles.
+  async fn subscribe(
+    &self,
+  ) -> Result<
+    BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+    BrokerError,
+  > {
+    let subscriber = match &self.queue_group {
+      Some(group) => {
+        self
+          .client
+          .queue_subscribe(self.subject.clone(), group.clone())
+          .map_err(BrokerError::from)
+          .await?
+      }
+      None => {
+        self
+          .client
+          .subscribe(self.subject.clone())
+          .map_err(BrokerError::from)
+          .await?
+      }
+    };
+    let messages = subscriber.map(|msg| {
+      Ok((
+        msg.payload,
+        Arc::new(AckNoop) as Arc<dyn AckTrait + Send + Sync>,
+      ))
+    });
+    Ok(self.subscriptions.track(messages))
+  }
+}
+
+#[async_trait]
+impl UnSubTrait for CoreSubscriber {
+  /// Ends the streams returned by `subscribe`, unsubscribing from the
+  /// subject.
+  async fn unsubscribe(&self) -> Result<(), UnSubError> {
+    self.subscriptions.abort_all();
+    Ok(())
+  }
+}
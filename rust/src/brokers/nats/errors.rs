@@ -5,6 +5,8 @@ use ::thiserror::Error;
 
 use crate::errors::BrokerError;
 
+use super::admin::ConfigDrift;
+
 /// Error type for NATS SubFetcher operations.
 #[derive(Error, Debug)]
 pub enum NatsSubFetcherError {
@@ -15,6 +17,49 @@ pub enum NatsSubFetcherError {
   ),
 }
 
+/// Error type for NATS stream and consumer administration.
+#[derive(Error, Debug)]
+pub enum NatsAdminError {
+  /// Error of the request to the server.
+  #[error("NATS JetStream Request Error: {0}")]
+  Request(#[from] BrokerError),
+  /// The stream doesn't exist.
+  #[error("Stream not found: {0}")]
+  StreamNotFound(String),
+  /// The consumer doesn't exist.
+  #[error("Consumer not found: {0}")]
+  ConsumerNotFound(String),
+  /// The consumer configuration has neither a name nor a durable name.
+  #[error("Consumer configuration has no name")]
+  UnnamedConsumer,
+  /// The configuration on the server differs from the desired one.
+  #[error("Configuration drift in {} field(s)", .0.len())]
+  Drift(Vec<ConfigDrift>),
+  /// Error while comparing configurations.
+  #[error("Configuration Serialization Error: {0}")]
+  Serialization(#[from] ::serde_json::Error),
+}
+
+/// Error type for typed object store operations.
+#[derive(Error, Debug)]
+pub enum NatsObjectStoreError {
+  /// Error of the request to the server.
+  #[error("NATS Object Store Request Error: {0}")]
+  Request(#[from] BrokerError),
+  /// The object doesn't exist.
+  #[error("Object not found: {0}")]
+  NotFound(String),
+  /// Error while reading the object.
+  #[error("Object Read Error: {0}")]
+  Io(#[from] ::std::io::Error),
+  /// The encoder failed.
+  #[error("Encode Error: {0}")]
+  Encode(Box<dyn ::std::error::Error + Send + Sync>),
+  /// The decoder failed.
+  #[error("Decode Error: {0}")]
+  Decode(Box<dyn ::std::error::Error + Send + Sync>),
+}
+
 impl<T> From<NatsKindError<T>> for BrokerError
 where
   T: Debug + Display + Clone + PartialEq + Send + Sync + 'static,
@@ -0,0 +1,139 @@
+use ::std::sync::Arc;
+use ::std::sync::atomic::{AtomicBool, Ordering};
+
+use ::async_nats::{ConnectOptions, Event};
+use ::tokio::sync::broadcast::{Receiver, Sender, channel};
+
+/// State change of a NATS connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+  /// The client connected to the server for the first time.
+  Connected,
+  /// The connection to the server was lost.
+  Disconnected,
+  /// The client connected again after being disconnected.
+  Reconnected,
+  /// Messages of the subscription with this id were dropped because the
+  /// subscriber could not keep up.
+  SlowConsumer(u64),
+  /// The server is shutting down and asks clients to move to another server.
+  LameDuckMode,
+  /// The client is draining its subscriptions.
+  Draining,
+  /// The connection was closed and won't reconnect.
+  Closed,
+  /// The server or the client reported an error.
+  Error(String),
+}
+
+impl ConnectionEvent {
+  fn from_event(event: Event, connected: &AtomicBool) -> Self {
+    match event {
+      Event::Connected if connected.swap(true, Ordering::AcqRel) => {
+        Self::Reconnected
+      }
+      Event::Connected => Self::Connected,
+      Event::Disconnected => Self::Disconnected,
+      Event::SlowConsumer(sid) => Self::SlowConsumer(sid),
+      Event::LameDuckMode => Self::LameDuckMode,
+      Event::Draining => Self::Draining,
+      Event::Closed => Self::Closed,
+      Event::ServerError(err) => Self::Error(err.to_string()),
+      Event::ClientError(err) => Self::Error(err.to_string()),
+    }
+  }
+}
+
+/// Broadcasts the [`ConnectionEvent`]s of a NATS connection to any number
+/// of observers.
+///
+/// Install it on the connection options with [`ConnectionEvents::options`],
+/// then observe the events with [`ConnectionEvents::subscribe`].
+#[derive(Debug, Clone)]
+pub struct ConnectionEvents {
+  sender: Sender<ConnectionEvent>,
+  connected: Arc<AtomicBool>,
+}
+
+impl ConnectionEvents {
+  /// Creates a broadcaster keeping up to `capacity` events for slow
+  /// observers.
+  ///
+  /// Observers lagging further behind skip the oldest events.
+  pub fn new(capacity: usize) -> Self {
+    let (sender, _) = channel(capacity);
+    Self {
+      sender,
+      connected: Arc::new(AtomicBool::new(false)),
+    }
+  }
+
+  /// Installs the event callback broadcasting the events of the connection.
+  ///
+  /// This replaces any event callback previously set on `options`.
+  ///
+  /// # Parameters
+  /// - `options`: Options of the connection to observe.
+  ///
+  /// # Returns
+  /// The options with the event callback set
+  pub fn options(&self, options: ConnectOptions) -> ConnectOptions {
+    let sender = self.sender.clone();
+    let connected = self.connected.clone();
+    options.event_callback(move |event| {
+      let event = ConnectionEvent::from_event(event, &connected);
+      // Nobody observing the events isn't an error.
+      let _ = sender.send(event);
+      async {}
+    })
+  }
+
+  /// Returns a receiver of the events broadcast from now on.
+  pub fn subscribe(&self) -> Receiver<ConnectionEvent> {
+    self.sender.subscribe()
+  }
+
+  #[cfg(test)]
+  pub(super) fn send(&self, event: ConnectionEvent) {
+    let _ = self.sender.send(event);
+  }
+}
+
+impl Default for ConnectionEvents {
+  fn default() -> Self {
+    Self::new(64)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_reconnected() {
+    let connected = AtomicBool::new(false);
+
+    assert_eq!(
+      ConnectionEvent::from_event(Event::Connected, &connected),
+      ConnectionEvent::Connected
+    );
+    assert_eq!(
+      ConnectionEvent::from_event(Event::Disconnected, &connected),
+      ConnectionEvent::Disconnected
+    );
+    assert_eq!(
+      ConnectionEvent::from_event(Event::Connected, &connected),
+      ConnectionEvent::Reconnected
+    );
+  }
+
+  #[test]
+  fn test_slow_consumer() {
+    let connected = AtomicBool::new(true);
+
+    assert_eq!(
+      ConnectionEvent::from_event(Event::SlowConsumer(7), &connected),
+      ConnectionEvent::SlowConsumer(7)
+    );
+  }
+}
@@ -0,0 +1,20 @@
+use ::async_nats::Client;
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+
+use super::super::traits::PubBrokerTrait;
+use crate::errors::BrokerError;
+
+#[async_trait]
+impl PubBrokerTrait for Client {
+  /// Publishes the payload with NATS core, without waiting for any
+  /// acknowledgment from a stream.
+  async fn publish(
+    &self,
+    topic: &str,
+    payload: Bytes,
+  ) -> Result<(), BrokerError> {
+    self.publish(topic.to_string(), payload).await?;
+    Ok(())
+  }
+}
@@ -2,7 +2,8 @@ use ::std::sync::Arc;
 
 use ::async_nats::jetstream::Context;
 use ::async_nats::jetstream::consumer::{
-  PullConsumer as PullCons, PushConsumer as PushCons,
+  OrderedPullConsumer as OrderedPullCons, PullConsumer as PullCons,
+  PushConsumer as PushCons,
 };
 use ::async_trait::async_trait;
 use ::bytes::Bytes;
@@ -11,6 +12,7 @@ use ::futures::{StreamExt, TryFutureExt, TryStreamExt};
 use ::std::boxed::Box;
 
 use super::super::traits::{PubBrokerTrait, SubBrokerTrait};
+use crate::ack_noop::AckNoop;
 use crate::errors::BrokerError;
 use crate::traits::AckTrait;
 
@@ -64,4 +66,31 @@ macro_rules! impl_sub_ctx_trait {
 impl_sub_ctx_trait!(PullCons);
 impl_sub_ctx_trait!(PushCons);
 
-// TODO: Need idea to implement SubCtxTrait for Ordered Message
+#[async_trait]
+impl SubBrokerTrait for OrderedPullCons {
+  /// Streams the messages in order, without gaps. The consumer is recreated
+  /// from the last received sequence when a gap is detected.
+  ///
+  /// Ordered consumers don't acknowledge messages, so the yielded handles are
+  /// no-ops.
+  async fn subscribe(
+    &self,
+  ) -> Result<
+    BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+    BrokerError,
+  > {
+    let messages = self
+      .clone()
+      .messages()
+      .map_err(BrokerError::from)
+      .await?
+      .map_err(BrokerError::from)
+      .map_ok(|msg| {
+        (
+          msg.message.payload,
+          Arc::new(AckNoop) as Arc<dyn AckTrait + Send + Sync>,
+        )
+      });
+    Ok(messages.boxed())
+  }
+}
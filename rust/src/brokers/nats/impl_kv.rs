@@ -0,0 +1,19 @@
+use ::async_nats::jetstream::kv::Store;
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+
+use super::super::traits::PubBrokerTrait;
+use crate::errors::BrokerError;
+
+#[async_trait]
+impl PubBrokerTrait for Store {
+  /// Puts the payload as the value of the key named by `topic`.
+  async fn publish(
+    &self,
+    topic: &str,
+    payload: Bytes,
+  ) -> Result<(), BrokerError> {
+    self.put(topic, payload).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,344 @@
+use ::std::error::Error as StdError;
+use ::std::sync::Arc;
+
+use ::async_nats::jetstream::kv::{Entry, Operation, Store};
+use ::async_trait::async_trait;
+use ::bytes::{Buf, BufMut, Bytes, BytesMut};
+use ::futures::stream::BoxStream;
+use ::futures::{TryFutureExt, TryStreamExt};
+use ::thiserror::Error;
+
+use crate::ack_noop::AckNoop;
+use crate::encoders::Decoder as DecoderTrait;
+use crate::errors::{BrokerError, UnSubError};
+use crate::traits::{AckTrait, UnSubTrait};
+
+use super::super::traits::SubBrokerTrait;
+use super::subscriptions::Subscriptions;
+
+/// Size of the header prepended to the values by [`KvWatcher`]: the
+/// operation, the revision and the key length.
+const HEADER_LEN: usize = 1 + 8 + 4;
+
+/// Error type for the KV change decoder.
+#[derive(Error, Debug)]
+pub enum KvError {
+  /// The payload wasn't produced by [`KvWatcher`].
+  #[error("Malformed KV entry header")]
+  MalformedHeader,
+  /// The inner decoder failed.
+  #[error("Value Error: {0}")]
+  Value(Box<dyn StdError + Send + Sync>),
+}
+
+/// Operation of a change of a KV bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KvOperation {
+  /// A value was put.
+  Put,
+  /// The key was deleted.
+  Delete,
+  /// The key and its history were purged.
+  Purge,
+}
+
+/// A change of a KV bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KvChange<T> {
+  /// The changed key.
+  pub key: String,
+  /// The revision of the change in the bucket.
+  pub revision: u64,
+  /// The operation of the change.
+  pub operation: KvOperation,
+  /// The new value, for [`KvOperation::Put`].
+  pub value: Option<T>,
+}
+
+/// Writes the key, the revision and the operation of an entry in front of
+/// its value.
+fn encode_entry(entry: Entry) -> Bytes {
+  let mut buf =
+    BytesMut::with_capacity(HEADER_LEN + entry.key.len() + entry.value.len());
+  buf.put_u8(match entry.operation {
+    Operation::Put => 0,
+    Operation::Delete => 1,
+    Operation::Purge => 2,
+  });
+  buf.put_u64(entry.revision);
+  buf.put_u32(entry.key.len() as u32);
+  buf.put_slice(entry.key.as_bytes());
+  buf.put_slice(&entry.value);
+  buf.freeze()
+}
+
+/// Splits a payload written by [`encode_entry`].
+fn decode_entry(
+  mut data: Bytes,
+) -> Result<(String, u64, KvOperation, Bytes), KvError> {
+  if data.len() < HEADER_LEN {
+    return Err(KvError::MalformedHeader);
+  }
+  let operation = match data.get_u8() {
+    0 => KvOperation::Put,
+    1 => KvOperation::Delete,
+    2 => KvOperation::Purge,
+    _ => return Err(KvError::MalformedHeader),
+  };
+  let revision = data.get_u64();
+  let key_len = data.get_u32() as usize;
+  if data.len() < key_len {
+    return Err(KvError::MalformedHeader);
+  }
+  let key = String::from_utf8(data.split_to(key_len).to_vec())
+    .map_err(|_| KvError::MalformedHeader)?;
+  Ok((key, revision, operation, data))
+}
+
+/// Watches the changes of a JetStream KV bucket.
+///
+/// Each change is yielded with its key, revision and operation, so the
+/// payloads must be decoded with [`KvDecoder`]. Acknowledgment handles are
+/// no-ops.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use serde::{Deserialize, Serialize};
+/// use futures::StreamExt;
+/// use object_transfer::{Pub, Sub, SubOpt, traits::{PubTrait, SubTrait}};
+/// use object_transfer::brokers::nats::{KvDecoder, KvWatcher};
+/// use object_transfer::encoders::{JSONDecoder, JSONEncoder};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Settings {
+///   verbose: bool,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let client = async_nats::connect("demo.nats.io").await?;
+///   let js = async_nats::jetstream::new(client);
+///   let store = js.get_key_value("config").await?;
+///
+///   let watcher = Arc::new(KvWatcher::new(store.clone()).key("app.>"));
+///   let sub = Sub::new(
+///     watcher.clone(),
+///     watcher,
+///     Arc::new(KvDecoder::new(Arc::new(JSONDecoder::<Settings>::new()))),
+///     SubOpt::new(),
+///   );
+///   let publisher: Pub<Settings, _> =
+///     Pub::new(Arc::new(store), "app.settings", Arc::new(JSONEncoder::new()));
+///
+///   let mut changes = sub.subscribe().await?;
+///   publisher.publish(&Settings { verbose: true }).await?;
+///   if let Some(Ok((change, _ack))) = changes.next().await {
+///     println!("{} changed at revision {}", change.key, change.revision);
+///   }
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct KvWatcher {
+  store: Store,
+  key: Option<String>,
+  latest_values: bool,
+  watches: Subscriptions,
+}
+
+impl KvWatcher {
+  /// Creates a watcher of all the keys of the bucket.
+  ///
+  /// # Parameters
+  /// - `store`: The KV bucket to watch.
+  pub fn new(store: Store) -> Self {
+    Self {
+      store,
+      key: None,
+      latest_values: false,
+      watches: Subscriptions::default(),
+    }
+  }
+
+  /// Watches only the given key.
+  ///
+  /// # Parameters
+  /// - `key`: The key to watch. Wildcards (`*`, `>`) are allowed.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn key(mut self, key: impl Into<String>) -> Self {
+    self.key = Some(key.into());
+    self
+  }
+
+  /// Yields the latest change of each watched key, i.e. its current value
+  /// or its deletion, before their new changes. Older revisions aren't
+  /// replayed.
+  ///
+  /// By default, only the changes made after subscribing are yielded.
+  ///
+  /// # Parameters
+  /// - `latest_values`: Whether the latest changes are yielded.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn latest_values(mut self, latest_values: bool) -> Self {
+    self.latest_values = latest_values;
+    self
+  }
+}
+
+#[async_trait]
+impl SubBrokerTrait for KvWatcher {
+  /// Watches the bucket, yielding each change along with a no-op
+  /// acknowledgment handle.
+  async fn subscribe(
+    &self,
+  ) -> Result<
+    BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+    BrokerError,
+  > {
+    let key = self.key.as_deref().unwrap_or(">");
+    let watch = if self.latest_values {
+      self
+        .store
+        .watch_with_history(key)
+        .map_err(BrokerError::from)
+        .await?
+    } else {
+      self.store.watch(key).map_err(BrokerError::from).await?
+    };
+    let changes = watch.map_err(BrokerError::from).map_ok(|entry| {
+      (
+        encode_entry(entry),
+        Arc::new(AckNoop) as Arc<dyn AckTrait + Send + Sync>,
+      )
+    });
+    Ok(self.watches.track(changes))
+  }
+}
+
+#[async_trait]
+impl UnSubTrait for KvWatcher {
+  /// Ends the streams returned by `subscribe`.
+  async fn unsubscribe(&self) -> Result<(), UnSubError> {
+    self.watches.abort_all();
+    Ok(())
+  }
+}
+
+/// Decoder reading the changes yielded by [`KvWatcher`].
+///
+/// The values are decoded with the inner decoder.
+pub struct KvDecoder<T, E: StdError + Send + Sync> {
+  inner: Arc<dyn DecoderTrait<Item = T, Error = E> + Send + Sync>,
+}
+
+impl<T, E> KvDecoder<T, E>
+where
+  T: Send + Sync,
+  E: StdError + Send + Sync + 'static,
+{
+  /// Creates a new KV change decoder.
+  ///
+  /// # Parameters
+  /// - `inner`: Decoder for the values.
+  pub fn new(
+    inner: Arc<dyn DecoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> Self {
+    Self { inner }
+  }
+}
+
+impl<T, E> DecoderTrait for KvDecoder<T, E>
+where
+  T: Send + Sync,
+  E: StdError + Send + Sync + 'static,
+{
+  type Item = KvChange<T>;
+  type Error = KvError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    let (key, revision, operation, value) = decode_entry(data)?;
+    let value = match operation {
+      KvOperation::Put => Some(
+        self
+          .inner
+          .decode(value)
+          .map_err(|e| KvError::Value(Box::new(e)))?,
+      ),
+      KvOperation::Delete | KvOperation::Purge => None,
+    };
+    Ok(KvChange {
+      key,
+      revision,
+      operation,
+      value,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::async_nats::jetstream::kv::Entry;
+
+  use super::*;
+  use crate::encoders::{JSONDecoder, RawDecoder};
+  use crate::tests::entity::TestEntity;
+
+  fn entry(operation: Operation, value: &'static [u8]) -> Entry {
+    Entry {
+      bucket: "config".to_string(),
+      key: "app.settings".to_string(),
+      value: Bytes::from_static(value),
+      revision: 7,
+      delta: 0,
+      created: ::std::time::SystemTime::UNIX_EPOCH.into(),
+      operation,
+      seen_current: false,
+    }
+  }
+
+  #[test]
+  fn test_put() {
+    let decoder = KvDecoder::new(Arc::new(JSONDecoder::<TestEntity>::new()));
+    let data =
+      encode_entry(entry(Operation::Put, br#"{"id":1,"name":"settings"}"#));
+
+    let change = decoder.decode(data).unwrap();
+
+    assert_eq!(
+      change,
+      KvChange {
+        key: "app.settings".to_string(),
+        revision: 7,
+        operation: KvOperation::Put,
+        value: Some(TestEntity::new(1, "settings")),
+      }
+    );
+  }
+
+  #[test]
+  fn test_delete_has_no_value() {
+    let decoder = KvDecoder::new(Arc::new(JSONDecoder::<TestEntity>::new()));
+
+    let change = decoder
+      .decode(encode_entry(entry(Operation::Delete, b"")))
+      .unwrap();
+
+    assert_eq!(change.operation, KvOperation::Delete);
+    assert_eq!(change.value, None);
+  }
+
+  #[test]
+  fn test_malformed_header() {
+    let decoder = KvDecoder::new(Arc::new(RawDecoder::<Bytes>::new()));
+
+    let result = decoder.decode(Bytes::from_static(b"\x00\x01"));
+
+    assert!(matches!(result, Err(KvError::MalformedHeader)));
+  }
+}
@@ -0,0 +1,258 @@
+use ::std::sync::Arc;
+
+use ::async_nats::jetstream::object_store::{
+  DeleteErrorKind, GetErrorKind, Object, ObjectInfo, ObjectMetadata,
+  ObjectStore,
+};
+use ::bytes::Bytes;
+use ::futures::stream::BoxStream;
+use ::futures::{StreamExt, TryStreamExt};
+use ::tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::encoders::{Decoder, Encoder};
+use crate::errors::BrokerError;
+
+use super::errors::NatsObjectStoreError;
+
+/// A change of an object store bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectChange<T> {
+  /// The information of the changed object. Its `deleted` flag is set for
+  /// deletions.
+  pub info: ObjectInfo,
+  /// The new object, unless it was deleted.
+  pub value: Option<T>,
+}
+
+/// Opens an object for reading.
+async fn open(
+  store: &ObjectStore,
+  name: &str,
+) -> Result<Object, NatsObjectStoreError> {
+  store.get(name).await.map_err(|e| match e.kind() {
+    GetErrorKind::NotFound => NatsObjectStoreError::NotFound(name.to_string()),
+    _ => BrokerError::from(e).into(),
+  })
+}
+
+/// Fetches and decodes an object.
+async fn fetch<T, DE>(
+  store: &ObjectStore,
+  decoder: &(dyn Decoder<Item = T, Error = DE> + Send + Sync),
+  name: &str,
+) -> Result<T, NatsObjectStoreError>
+where
+  T: Send + Sync,
+  DE: ::std::error::Error + Send + Sync + 'static,
+{
+  let mut object = open(store, name).await?;
+  let mut data = Vec::with_capacity(object.info().size);
+  object.read_to_end(&mut data).await?;
+  decoder
+    .decode(Bytes::from(data))
+    .map_err(|e| NatsObjectStoreError::Decode(Box::new(e)))
+}
+
+/// Typed access to a JetStream object store bucket.
+///
+/// Objects are encoded and decoded with the given encoder and decoder, and
+/// stored in chunks by the object store, so they aren't limited by the
+/// maximum message size of the server. Payloads too large to be held in
+/// memory can be streamed with [`put_reader`](Self::put_reader) and
+/// [`get_reader`](Self::get_reader), bypassing the encoder and decoder.
+///
+/// # Type Parameters
+///
+/// * `T` - The type of the stored objects.
+/// * `EE` - The error type of the encoder.
+/// * `DE` - The error type of the decoder.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use serde::{Deserialize, Serialize};
+/// use object_transfer::brokers::nats::TypedObjectStore;
+/// use object_transfer::encoders::{MessagePackDecoder, MessagePackEncoder};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Model {
+///   weights: Vec<f32>,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let client = async_nats::connect("demo.nats.io").await?;
+///   let js = async_nats::jetstream::new(client);
+///   let bucket = js.get_object_store("models").await?;
+///
+///   let models = TypedObjectStore::new(
+///     bucket,
+///     Arc::new(MessagePackEncoder::new()),
+///     Arc::new(MessagePackDecoder::new()),
+///   );
+///   models.put("v1", &Model { weights: vec![0.5; 1024] }).await?;
+///   let model: Model = models.get("v1").await?;
+///   println!("{} weights", model.weights.len());
+///   Ok(())
+/// }
+/// ```
+pub struct TypedObjectStore<T, EE, DE> {
+  store: ObjectStore,
+  encoder: Arc<dyn Encoder<Item = T, Error = EE> + Send + Sync>,
+  decoder: Arc<dyn Decoder<Item = T, Error = DE> + Send + Sync>,
+  chunk_size: Option<usize>,
+}
+
+impl<T, EE, DE> TypedObjectStore<T, EE, DE>
+where
+  T: Send + Sync,
+  EE: ::std::error::Error + Send + Sync + 'static,
+  DE: ::std::error::Error + Send + Sync + 'static,
+{
+  /// Creates a typed handle of an object store bucket.
+  ///
+  /// # Parameters
+  /// - `store`: The object store bucket.
+  /// - `encoder`: Encoder of the stored objects.
+  /// - `decoder`: Decoder of the stored objects.
+  pub fn new(
+    store: ObjectStore,
+    encoder: Arc<dyn Encoder<Item = T, Error = EE> + Send + Sync>,
+    decoder: Arc<dyn Decoder<Item = T, Error = DE> + Send + Sync>,
+  ) -> Self {
+    Self {
+      store,
+      encoder,
+      decoder,
+      chunk_size: None,
+    }
+  }
+
+  /// Sets the size of the chunks the objects are stored in.
+  ///
+  /// # Parameters
+  /// - `chunk_size`: The chunk size in bytes. By default, the object store
+  ///   uses 128 KiB chunks.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+    self.chunk_size = Some(chunk_size);
+    self
+  }
+
+  fn metadata(&self, name: &str) -> ObjectMetadata {
+    ObjectMetadata {
+      name: name.to_string(),
+      chunk_size: self.chunk_size,
+      ..Default::default()
+    }
+  }
+
+  /// Encodes and stores an object, replacing any object of the same name.
+  ///
+  /// # Parameters
+  /// - `name`: The name of the object.
+  /// - `item`: The object to store.
+  pub async fn put(
+    &self,
+    name: &str,
+    item: &T,
+  ) -> Result<ObjectInfo, NatsObjectStoreError> {
+    let data = self
+      .encoder
+      .encode(item)
+      .map_err(|e| NatsObjectStoreError::Encode(Box::new(e)))?;
+    self.put_reader(name, &mut data.as_ref()).await
+  }
+
+  /// Fetches and decodes an object.
+  ///
+  /// # Parameters
+  /// - `name`: The name of the object.
+  pub async fn get(&self, name: &str) -> Result<T, NatsObjectStoreError> {
+    fetch(&self.store, self.decoder.as_ref(), name).await
+  }
+
+  /// Stores the content of a reader as-is, chunk by chunk.
+  ///
+  /// # Parameters
+  /// - `name`: The name of the object.
+  /// - `reader`: The content of the object.
+  pub async fn put_reader(
+    &self,
+    name: &str,
+    reader: &mut (impl AsyncRead + Unpin),
+  ) -> Result<ObjectInfo, NatsObjectStoreError> {
+    let info = self
+      .store
+      .put(self.metadata(name), reader)
+      .await
+      .map_err(BrokerError::from)?;
+    Ok(info)
+  }
+
+  /// Returns a reader streaming the content of an object chunk by chunk.
+  ///
+  /// # Parameters
+  /// - `name`: The name of the object.
+  pub async fn get_reader(
+    &self,
+    name: &str,
+  ) -> Result<Object, NatsObjectStoreError> {
+    open(&self.store, name).await
+  }
+
+  /// Deletes an object.
+  ///
+  /// # Parameters
+  /// - `name`: The name of the object.
+  pub async fn delete(&self, name: &str) -> Result<(), NatsObjectStoreError> {
+    self.store.delete(name).await.map_err(|e| match e.kind() {
+      DeleteErrorKind::NotFound => {
+        NatsObjectStoreError::NotFound(name.to_string())
+      }
+      _ => BrokerError::from(e).into(),
+    })
+  }
+
+  /// Watches the bucket, yielding each object put or deleted after the
+  /// call.
+  ///
+  /// Objects put are fetched and decoded; deletions are yielded without
+  /// value. An object deleted or replaced before it could be fetched yields
+  /// an error, followed by the change that superseded it.
+  pub async fn watch(
+    &self,
+  ) -> Result<
+    BoxStream<'static, Result<ObjectChange<T>, NatsObjectStoreError>>,
+    NatsObjectStoreError,
+  >
+  where
+    T: 'static,
+  {
+    let watch = self.store.watch().await.map_err(BrokerError::from)?;
+    let store = self.store.clone();
+    let decoder = self.decoder.clone();
+    Ok(
+      watch
+        .map_err(|e| NatsObjectStoreError::from(BrokerError::from(e)))
+        .and_then(move |info| {
+          let store = store.clone();
+          let decoder = decoder.clone();
+          async move {
+            if info.deleted {
+              return Ok(ObjectChange { info, value: None });
+            }
+            let value = fetch(&store, decoder.as_ref(), &info.name).await?;
+            Ok(ObjectChange {
+              info,
+              value: Some(value),
+            })
+          }
+        })
+        .boxed(),
+    )
+  }
+}
@@ -1,7 +1,12 @@
 use ::std::sync::Arc;
+use ::std::time::Duration;
 
+use ::async_nats::datetime::DateTime;
 use ::async_nats::jetstream::{
-  consumer::pull::Config as PullConfig, stream::Config as StreamConfig,
+  consumer::pull::{Config as PullConfig, OrderedConfig},
+  consumer::push::Config as PushConfig,
+  consumer::{DeliverPolicy, ReplayPolicy},
+  stream::Config as StreamConfig,
 };
 
 /// Configuration options for creating an acknowledgment-based subscriber.
@@ -12,6 +17,9 @@ use ::async_nats::jetstream::{
 pub struct SubFetcherOpt {
   pub(super) stream_cfg: StreamConfig,
   pub(super) pull_cfg: PullConfig,
+  pub(super) batch: Option<BatchOpt>,
+  pub(super) ordered: bool,
+  pub(super) unsub_mode: UnSubMode,
 }
 
 impl SubFetcherOpt {
@@ -32,6 +40,9 @@ impl SubFetcherOpt {
         name: Some(name.clone().to_string()),
         ..PullConfig::default()
       },
+      batch: None,
+      ordered: false,
+      unsub_mode: UnSubMode::default(),
     }
   }
 
@@ -101,4 +112,421 @@ impl SubFetcherOpt {
     self.pull_cfg = pull_cfg;
     self
   }
+
+  /// Returns the ordered consumer configuration derived from the pull
+  /// consumer configuration.
+  pub(super) fn ordered_config(&self) -> OrderedConfig {
+    OrderedConfig {
+      description: self.pull_cfg.description.clone(),
+      filter_subject: self.pull_cfg.filter_subject.clone(),
+      filter_subjects: self.pull_cfg.filter_subjects.clone(),
+      replay_policy: self.pull_cfg.replay_policy,
+      deliver_policy: self.pull_cfg.deliver_policy,
+      headers_only: self.pull_cfg.headers_only,
+      metadata: self.pull_cfg.metadata.clone(),
+      ..OrderedConfig::default()
+    }
+  }
+
+  /// Delivers all the messages of the stream, starting from the oldest one.
+  ///
+  /// This is the default. Like the other delivery options, it only applies
+  /// when the consumer is created: an existing durable consumer keeps its
+  /// position.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn deliver_all(mut self) -> Self {
+    self.pull_cfg.deliver_policy = DeliverPolicy::All;
+    self
+  }
+
+  /// Delivers only the messages published after the consumer is created.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn deliver_new(mut self) -> Self {
+    self.pull_cfg.deliver_policy = DeliverPolicy::New;
+    self
+  }
+
+  /// Delivers the last message of the stream, then the new ones.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn deliver_last(mut self) -> Self {
+    self.pull_cfg.deliver_policy = DeliverPolicy::Last;
+    self
+  }
+
+  /// Delivers the last message of each subject, then the new ones.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn deliver_last_per_subject(mut self) -> Self {
+    self.pull_cfg.deliver_policy = DeliverPolicy::LastPerSubject;
+    self
+  }
+
+  /// Delivers the messages starting from a stream sequence number.
+  ///
+  /// # Arguments
+  /// * `sequence` - The sequence number of the first message to deliver
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn start_sequence(mut self, sequence: u64) -> Self {
+    self.pull_cfg.deliver_policy = DeliverPolicy::ByStartSequence {
+      start_sequence: sequence,
+    };
+    self
+  }
+
+  /// Delivers the messages published at or after a point in time.
+  ///
+  /// # Arguments
+  /// * `time` - The point in time, e.g. a `std::time::SystemTime`
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn start_time(mut self, time: impl Into<DateTime>) -> Self {
+    self.pull_cfg.deliver_policy = DeliverPolicy::ByStartTime {
+      start_time: time.into(),
+    };
+    self
+  }
+
+  /// Sets the rate at which the stored messages are replayed.
+  ///
+  /// # Arguments
+  /// * `replay_policy` - `ReplayPolicy::Instant` (the default) to deliver
+  ///   messages as fast as possible, or `ReplayPolicy::Original` to deliver
+  ///   them at the rate they were published
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn replay_policy(mut self, replay_policy: ReplayPolicy) -> Self {
+    self.pull_cfg.replay_policy = replay_policy;
+    self
+  }
+
+  /// Consumes the stream with an ordered consumer.
+  ///
+  /// Ordered consumers deliver the messages strictly in order and without
+  /// gaps, recreating themselves from the last received sequence when a gap
+  /// is detected. They are ephemeral and don't acknowledge messages, so the
+  /// durable name and the batch limits aren't used. The subject filters, the
+  /// delivery policy and the replay policy of the pull configuration are.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn ordered(mut self) -> Self {
+    self.ordered = true;
+    self
+  }
+
+  /// Sets what `unsubscribe` does besides ending the local streams.
+  ///
+  /// # Arguments
+  /// * `unsub_mode` - The unsubscribe mode, [`UnSubMode::DeleteConsumer`]
+  ///   by default
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn unsub_mode(mut self, unsub_mode: UnSubMode) -> Self {
+    self.unsub_mode = unsub_mode;
+    self
+  }
+
+  /// Fetches a single batch of messages instead of streaming continuously.
+  ///
+  /// With this option, the stream returned by `subscribe` ends once the
+  /// batch is complete or has expired, which suits cron-style workers.
+  ///
+  /// # Arguments
+  /// * `batch` - The limits of the batch
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn batch(mut self, batch: BatchOpt) -> Self {
+    self.batch = Some(batch);
+    self
+  }
+}
+
+/// What [`SubFetcher`](super::SubFetcher) does on `unsubscribe`, besides
+/// ending the streams returned by `subscribe`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnSubMode {
+  /// Keeps the consumer on the server, so that a durable consumer resumes
+  /// from its position on the next subscription.
+  Keep,
+  /// Deletes the consumer, named by its durable name or else by its name.
+  #[default]
+  DeleteConsumer,
+  /// Deletes the whole stream, e.g. for temporary streams in tests.
+  DeleteStream,
+}
+
+/// Limits of a single batch fetched from a pull consumer.
+///
+/// A batch ends when `max_messages` messages or `max_bytes` bytes have been
+/// received, or when it expires, whichever comes first.
+#[derive(Debug, Clone)]
+pub struct BatchOpt {
+  pub(super) max_messages: usize,
+  pub(super) max_bytes: usize,
+  pub(super) expires: Duration,
+}
+
+impl BatchOpt {
+  /// Creates a new `BatchOpt` fetching up to `max_messages` messages, with no
+  /// byte limit, expiring after 30 seconds.
+  ///
+  /// # Arguments
+  /// * `max_messages` - The maximum number of messages in the batch
+  ///
+  /// # Returns
+  /// A new `BatchOpt` instance
+  pub fn new(max_messages: usize) -> Self {
+    Self {
+      max_messages,
+      max_bytes: 0,
+      expires: Duration::from_secs(30),
+    }
+  }
+
+  /// Sets the maximum total size of the messages in the batch.
+  ///
+  /// # Arguments
+  /// * `max_bytes` - The maximum size in bytes, or `0` for no limit
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+    self.max_bytes = max_bytes;
+    self
+  }
+
+  /// Sets how long the server waits for messages before ending the batch.
+  ///
+  /// # Arguments
+  /// * `expires` - The time after which an incomplete batch ends
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn expires(mut self, expires: Duration) -> Self {
+    self.expires = expires;
+    self
+  }
+}
+
+/// Configuration options for creating a push-based subscriber.
+///
+/// This struct provides a builder pattern for configuring NATS JetStream
+/// consumers that push messages to a deliver subject.
+#[derive(Debug, Clone)]
+pub struct PushSubscriberOpt {
+  pub(super) stream_cfg: StreamConfig,
+  pub(super) push_cfg: PushConfig,
+}
+
+impl PushSubscriberOpt {
+  /// Creates a new `PushSubscriberOpt` with the specified name and deliver
+  /// subject.
+  ///
+  /// # Arguments
+  /// * `name` - The name for both the stream and consumer
+  /// * `deliver_subject` - The subject the consumer pushes messages to
+  ///
+  /// # Returns
+  /// A new `PushSubscriberOpt` instance with default settings
+  pub fn new(name: Arc<str>, deliver_subject: impl Into<String>) -> Self {
+    Self {
+      stream_cfg: StreamConfig {
+        name: name.to_string(),
+        ..Default::default()
+      },
+      push_cfg: PushConfig {
+        name: Some(name.to_string()),
+        deliver_subject: deliver_subject.into(),
+        ..PushConfig::default()
+      },
+    }
+  }
+
+  /// Sets the subjects that the stream should listen to.
+  ///
+  /// # Arguments
+  /// * `subjects` - A vector of subject patterns to subscribe to
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn subjects(mut self, subjects: Vec<impl Into<String>>) -> Self {
+    self.stream_cfg.subjects = subjects.into_iter().map(Into::into).collect();
+    self
+  }
+
+  /// Sets the durable name for the consumer.
+  ///
+  /// # Arguments
+  /// * `durable_name` - The durable name for the consumer
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn durable_name(mut self, durable_name: impl Into<String>) -> Self {
+    self.push_cfg.durable_name = Some(durable_name.into());
+    self
+  }
+
+  /// Sets the deliver group, so that each message is delivered to only one
+  /// of the subscribers of the group.
+  ///
+  /// # Arguments
+  /// * `deliver_group` - The name of the queue group
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn deliver_group(mut self, deliver_group: impl Into<String>) -> Self {
+    self.push_cfg.deliver_group = Some(deliver_group.into());
+    self
+  }
+
+  /// Sets the interval of the idle heartbeats sent by the server while no
+  /// message is delivered.
+  ///
+  /// When no heartbeat arrives for twice the interval, the stream yields an
+  /// error without ending, so that stalled consumers can be detected.
+  ///
+  /// # Arguments
+  /// * `idle_heartbeat` - The heartbeat interval
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn idle_heartbeat(mut self, idle_heartbeat: Duration) -> Self {
+    self.push_cfg.idle_heartbeat = idle_heartbeat;
+    self
+  }
+
+  /// Enables flow control, so that the server slows down when the
+  /// subscriber falls behind.
+  ///
+  /// Flow control requires an idle heartbeat to be set.
+  ///
+  /// # Arguments
+  /// * `flow_control` - Whether flow control is enabled
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn flow_control(mut self, flow_control: bool) -> Self {
+    self.push_cfg.flow_control = flow_control;
+    self
+  }
+
+  /// Sets the complete stream configuration.
+  ///
+  /// # Arguments
+  /// * `stream_cfg` - The stream configuration to use
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn stream_config(mut self, stream_cfg: StreamConfig) -> Self {
+    self.stream_cfg = stream_cfg;
+    self
+  }
+
+  /// Sets the complete push consumer configuration.
+  ///
+  /// # Arguments
+  /// * `push_cfg` - The push consumer configuration to use
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn push_config(mut self, push_cfg: PushConfig) -> Self {
+    self.push_cfg = push_cfg;
+    self
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::std::time::{Duration, SystemTime};
+
+  use super::*;
+
+  #[test]
+  fn test_deliver_policy() {
+    let opt = SubFetcherOpt::new(Arc::from("events"));
+    assert_eq!(opt.pull_cfg.deliver_policy, DeliverPolicy::All);
+
+    let opt = opt.deliver_last_per_subject();
+    assert_eq!(opt.pull_cfg.deliver_policy, DeliverPolicy::LastPerSubject);
+
+    let opt = opt.start_sequence(42);
+    assert_eq!(
+      opt.pull_cfg.deliver_policy,
+      DeliverPolicy::ByStartSequence { start_sequence: 42 }
+    );
+  }
+
+  #[test]
+  fn test_start_time() {
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+    let opt = SubFetcherOpt::new(Arc::from("events")).start_time(time);
+
+    assert_eq!(
+      opt.pull_cfg.deliver_policy,
+      DeliverPolicy::ByStartTime {
+        start_time: DateTime::from(time)
+      }
+    );
+  }
+
+  #[test]
+  fn test_ordered_config() {
+    let opt = SubFetcherOpt::new(Arc::from("events"))
+      .durable_name("projection")
+      .start_sequence(7)
+      .ordered();
+
+    let cfg = opt.ordered_config();
+
+    assert!(opt.ordered);
+    assert_eq!(cfg.name, None);
+    assert_eq!(
+      cfg.deliver_policy,
+      DeliverPolicy::ByStartSequence { start_sequence: 7 }
+    );
+  }
+
+  #[test]
+  fn test_unsub_mode() {
+    let opt = SubFetcherOpt::new(Arc::from("events"));
+    assert_eq!(opt.unsub_mode, UnSubMode::DeleteConsumer);
+
+    let opt = opt.unsub_mode(UnSubMode::DeleteStream);
+    assert_eq!(opt.unsub_mode, UnSubMode::DeleteStream);
+  }
+
+  #[test]
+  fn test_replay_policy() {
+    let opt = SubFetcherOpt::new(Arc::from("events"))
+      .replay_policy(ReplayPolicy::Original);
+
+    assert_eq!(opt.pull_cfg.replay_policy, ReplayPolicy::Original);
+  }
+
+  #[test]
+  fn test_push_options() {
+    let opt = PushSubscriberOpt::new(Arc::from("events"), "deliver.events")
+      .deliver_group("workers")
+      .idle_heartbeat(Duration::from_secs(5))
+      .flow_control(true);
+
+    assert_eq!(opt.push_cfg.deliver_subject, "deliver.events");
+    assert_eq!(opt.push_cfg.deliver_group.as_deref(), Some("workers"));
+    assert_eq!(opt.push_cfg.idle_heartbeat, Duration::from_secs(5));
+    assert!(opt.push_cfg.flow_control);
+  }
 }
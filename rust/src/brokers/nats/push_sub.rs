@@ -0,0 +1,101 @@
+use ::std::sync::Arc;
+
+use ::async_nats::jetstream::consumer::PushConsumer;
+use ::async_nats::jetstream::{context::Context, stream::Stream as JStream};
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::futures::stream::BoxStream;
+use ::futures::{StreamExt, TryFutureExt};
+
+use crate::errors::{BrokerError, UnSubError};
+use crate::traits::{AckTrait, UnSubTrait};
+
+use super::super::traits::SubBrokerTrait;
+
+use super::errors::NatsSubFetcherError;
+use super::options::PushSubscriberOpt;
+use super::subscriptions::Subscriptions;
+
+/// Receives the messages pushed by a JetStream consumer to its deliver
+/// subject, using the configured stream options.
+#[derive(Debug)]
+pub struct PushSubscriber {
+  stream: JStream,
+  options: PushSubscriberOpt,
+  subscriptions: Subscriptions,
+}
+
+impl PushSubscriber {
+  /// Creates or reuses a JetStream stream based on the provided options.
+  ///
+  /// # Parameters
+  /// - `ctx`: JetStream context used to resolve or create the target stream.
+  /// - `options`: Configuration for the stream and push consumer.
+  pub async fn new(
+    ctx: Arc<Context>,
+    options: PushSubscriberOpt,
+  ) -> Result<Self, NatsSubFetcherError> {
+    let stream = ctx.get_or_create_stream(options.stream_cfg.clone()).await?;
+    Ok(Self {
+      stream,
+      options,
+      subscriptions: Subscriptions::default(),
+    })
+  }
+}
+
+#[async_trait]
+impl SubBrokerTrait for PushSubscriber {
+  /// Stream messages pushed by the consumer, yielding their payloads along
+  /// with the associated acknowledgment handles.
+  ///
+  /// Idle heartbeats and flow control messages are answered without being
+  /// yielded.
+  async fn subscribe(
+    &self,
+  ) -> Result<
+    BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+    BrokerError,
+  > {
+    let consumer: PushConsumer = self
+      .stream
+      .get_or_create_consumer(
+        &self.options.stream_cfg.name,
+        self.options.push_cfg.clone(),
+      )
+      .map_err(BrokerError::from)
+      .await?;
+    let messages = async_stream::try_stream! {
+      let mut msgs = consumer.subscribe().await?;
+      while let Some(result) = msgs.next().await {
+        yield result?;
+      }
+    };
+    Ok(self.subscriptions.track(messages))
+  }
+}
+
+#[async_trait]
+impl UnSubTrait for PushSubscriber {
+  /// Ends the streams returned by `subscribe`, then deletes the consumer
+  /// associated with this subscriber, identified by its durable name or,
+  /// failing that, its name.
+  ///
+  /// Returns [`UnSubError::NothingToUnsubscribe`] if the consumer has
+  /// neither a durable name nor a name.
+  async fn unsubscribe(&self) -> Result<(), UnSubError> {
+    self.subscriptions.abort_all();
+    let cfg = &self.options.push_cfg;
+    let name = cfg
+      .durable_name
+      .as_deref()
+      .or(cfg.name.as_deref())
+      .ok_or(UnSubError::NothingToUnsubscribe)?;
+    self
+      .stream
+      .delete_consumer(name)
+      .map_err(|e| UnSubError::BrokerError(e.into()))
+      .await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,375 @@
+use ::std::pin::pin;
+use ::std::sync::Arc;
+use ::std::time::Duration;
+
+use ::async_stream::stream;
+use ::async_trait::async_trait;
+use ::bytes::Bytes;
+use ::futures::StreamExt;
+use ::futures::future::{Either, select};
+use ::futures::stream::BoxStream;
+use ::tokio::sync::broadcast::error::RecvError;
+
+use crate::errors::{BrokerError, UnSubError};
+use crate::traits::{AckTrait, UnSubTrait};
+
+use super::super::traits::SubBrokerTrait;
+use super::events::{ConnectionEvent, ConnectionEvents};
+use super::subscriptions::Subscriptions;
+
+/// Keeps the streams of a NATS subscriber alive across reconnects.
+///
+/// When the connection observed by the [`ConnectionEvents`] is
+/// re-established, the stream returned by the wrapped subscriber is replaced
+/// with a new one, so the application keeps consuming the same stream. Errors
+/// of the wrapped stream are still yielded.
+///
+/// Connection events may be missed when the observer lags behind, in which
+/// case the stream subscribes again in case the connection was
+/// re-established meanwhile. When the wrapped stream ends, the stream waits
+/// for the connection to be re-established, so that a stream ending before
+/// the disconnection is reported isn't lost; it only ends once the
+/// connection is closed, or when unsubscribing. Wrapping a subscriber whose
+/// streams end on their own, such as a batch fetch, thus keeps the stream
+/// open until then.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use serde::Deserialize;
+/// use futures::StreamExt;
+/// use object_transfer::{Sub, SubOpt, traits::SubTrait};
+/// use object_transfer::brokers::nats::{
+///   ConnectionEvent, ConnectionEvents, Resubscriber, SubFetcher,
+///   SubFetcherOpt,
+/// };
+/// use object_transfer::encoders::JSONDecoder;
+///
+/// #[derive(Deserialize)]
+/// struct Event {
+///   id: u32,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let events = ConnectionEvents::default();
+///   let client = events
+///     .options(async_nats::ConnectOptions::new())
+///     .connect("demo.nats.io")
+///     .await?;
+///   let js = Arc::new(async_nats::jetstream::new(client));
+///   let fetcher =
+///     SubFetcher::new(js, SubFetcherOpt::new(Arc::from("events"))).await?;
+///   let broker = Arc::new(Resubscriber::new(fetcher, &events));
+///   let sub: Sub<Event, _> = Sub::new(
+///     broker.clone(),
+///     broker,
+///     Arc::new(JSONDecoder::new()),
+///     SubOpt::new(),
+///   );
+///
+///   let mut states = events.subscribe();
+///   tokio::spawn(async move {
+///     while let Ok(state) = states.recv().await {
+///       if let ConnectionEvent::SlowConsumer(sid) = state {
+///         eprintln!("subscription {sid} is falling behind");
+///       }
+///     }
+///   });
+///
+///   let mut stream = sub.subscribe().await?;
+///   while let Some(Ok((event, _ack))) = stream.next().await {
+///     println!("{}", event.id);
+///   }
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Resubscriber<B> {
+  inner: B,
+  events: ConnectionEvents,
+  retry_delay: Duration,
+  subscriptions: Subscriptions,
+}
+
+impl<B> Resubscriber<B> {
+  /// Wraps a subscriber of the connection observed by `events`.
+  ///
+  /// # Parameters
+  /// - `inner`: The subscriber whose streams are recreated.
+  /// - `events`: The events of the connection used by `inner`.
+  pub fn new(inner: B, events: &ConnectionEvents) -> Self {
+    Self {
+      inner,
+      events: events.clone(),
+      retry_delay: Duration::from_secs(1),
+      subscriptions: Subscriptions::default(),
+    }
+  }
+
+  /// Sets the delay before subscribing again when subscribing after a
+  /// reconnect fails. Defaults to 1 second.
+  ///
+  /// # Parameters
+  /// - `retry_delay`: The delay between attempts.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+    self.retry_delay = retry_delay;
+    self
+  }
+}
+
+enum Step<M> {
+  Message(Option<M>),
+  Event(Result<ConnectionEvent, RecvError>),
+}
+
+#[async_trait]
+impl<B> SubBrokerTrait for Resubscriber<B>
+where
+  B: SubBrokerTrait + Send + Sync,
+{
+  /// Subscribes with the wrapped subscriber, subscribing again whenever the
+  /// connection is re-established.
+  ///
+  /// Errors of the first subscription are returned; errors of the later
+  /// ones are yielded by the stream, and retried after the retry delay
+  /// until the connection is closed.
+  async fn subscribe(
+    &self,
+  ) -> Result<
+    BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+    BrokerError,
+  > {
+    let mut events = self.events.subscribe();
+    let mut messages = self.inner.subscribe().await?;
+    let stream = stream! {
+      let mut observing = true;
+      let mut resubscribe = false;
+      loop {
+        while resubscribe {
+          match self.inner.subscribe().await {
+            Ok(stream) => {
+              messages = stream;
+              resubscribe = false;
+            }
+            Err(err) => {
+              yield Err(err);
+              let mut delay = pin!(::tokio::time::sleep(self.retry_delay));
+              loop {
+                match select(delay.as_mut(), pin!(events.recv())).await {
+                  Either::Left(_) => break,
+                  Either::Right((
+                    Ok(ConnectionEvent::Closed) | Err(RecvError::Closed),
+                    _,
+                  )) => return,
+                  Either::Right(_) => {}
+                }
+              }
+            }
+          }
+        }
+        let step = if observing {
+          match select(messages.next(), pin!(events.recv())).await {
+            Either::Left((message, _)) => Step::Message(message),
+            Either::Right((event, _)) => Step::Event(event),
+          }
+        } else {
+          Step::Message(messages.next().await)
+        };
+        match step {
+          Step::Message(Some(message)) => yield message,
+          Step::Message(None) if !observing => break,
+          // The stream may end before the disconnection is reported: wait
+          // for the reconnect.
+          Step::Message(None) => loop {
+            match events.recv().await {
+              Ok(ConnectionEvent::Reconnected) | Err(RecvError::Lagged(_)) => {
+                resubscribe = true;
+                break;
+              }
+              Ok(ConnectionEvent::Closed) | Err(RecvError::Closed) => return,
+              Ok(_) => {}
+            }
+          },
+          // A missed event may have been a reconnect.
+          Step::Event(
+            Ok(ConnectionEvent::Reconnected) | Err(RecvError::Lagged(_)),
+          ) => resubscribe = true,
+          Step::Event(Ok(ConnectionEvent::Closed) | Err(RecvError::Closed)) => {
+            observing = false;
+          }
+          Step::Event(Ok(_)) => {}
+        }
+      }
+    };
+    Ok(self.subscriptions.track(stream))
+  }
+}
+
+#[async_trait]
+impl<B> UnSubTrait for Resubscriber<B>
+where
+  B: UnSubTrait + Send + Sync,
+{
+  /// Ends the streams returned by `subscribe`, then unsubscribes the wrapped
+  /// subscriber.
+  async fn unsubscribe(&self) -> Result<(), UnSubError> {
+    self.subscriptions.abort_all();
+    self.inner.unsubscribe().await
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::std::sync::atomic::{AtomicU8, Ordering};
+
+  use ::futures::stream;
+
+  use super::*;
+  use crate::ack_noop::AckNoop;
+  use crate::tests::error::MockBrokerErr;
+
+  /// Yields one message numbering the subscription, then waits forever, or
+  /// ends if `ends` is set.
+  struct Counter {
+    count: AtomicU8,
+    ends: bool,
+  }
+
+  impl Counter {
+    fn new(ends: bool) -> Self {
+      Self {
+        count: AtomicU8::new(0),
+        ends,
+      }
+    }
+  }
+
+  #[async_trait]
+  impl SubBrokerTrait for Counter {
+    async fn subscribe(
+      &self,
+    ) -> Result<
+      BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+      BrokerError,
+    > {
+      let count = self.count.fetch_add(1, Ordering::SeqCst) + 1;
+      let ack: Arc<dyn AckTrait + Send + Sync> = Arc::new(AckNoop);
+      let messages = stream::iter([Ok((Bytes::from(vec![count]), ack))]);
+      if self.ends {
+        Ok(messages.boxed())
+      } else {
+        Ok(messages.chain(stream::pending()).boxed())
+      }
+    }
+  }
+
+  #[async_trait]
+  impl UnSubTrait for Counter {
+    async fn unsubscribe(&self) -> Result<(), UnSubError> {
+      Ok(())
+    }
+  }
+
+  #[tokio::test]
+  async fn test_resubscribe_on_reconnect() {
+    let events = ConnectionEvents::default();
+    let broker = Resubscriber::new(Counter::new(false), &events);
+    let mut messages = broker.subscribe().await.unwrap();
+
+    let (payload, _) = messages.next().await.unwrap().unwrap();
+    assert_eq!(payload.as_ref(), &[1]);
+
+    events.send(ConnectionEvent::Disconnected);
+    events.send(ConnectionEvent::Reconnected);
+    let (payload, _) = messages.next().await.unwrap().unwrap();
+    assert_eq!(payload.as_ref(), &[2]);
+  }
+
+  #[tokio::test]
+  async fn test_resubscribe_on_lag() {
+    let events = ConnectionEvents::new(1);
+    let broker = Resubscriber::new(Counter::new(false), &events);
+    let mut messages = broker.subscribe().await.unwrap();
+
+    let (payload, _) = messages.next().await.unwrap().unwrap();
+    assert_eq!(payload.as_ref(), &[1]);
+
+    // The reconnect is lost among the later events.
+    events.send(ConnectionEvent::Reconnected);
+    events.send(ConnectionEvent::SlowConsumer(1));
+    events.send(ConnectionEvent::SlowConsumer(1));
+    let (payload, _) = messages.next().await.unwrap().unwrap();
+    assert_eq!(payload.as_ref(), &[2]);
+  }
+
+  #[tokio::test]
+  async fn test_resubscribe_after_stream_end() {
+    let events = ConnectionEvents::default();
+    let broker = Resubscriber::new(Counter::new(true), &events);
+    let mut messages = broker.subscribe().await.unwrap();
+
+    let (payload, _) = messages.next().await.unwrap().unwrap();
+    assert_eq!(payload.as_ref(), &[1]);
+
+    // The stream ends before the disconnection is reported.
+    events.send(ConnectionEvent::Disconnected);
+    events.send(ConnectionEvent::Reconnected);
+    let (payload, _) = messages.next().await.unwrap().unwrap();
+    assert_eq!(payload.as_ref(), &[2]);
+
+    events.send(ConnectionEvent::Closed);
+    assert!(messages.next().await.is_none());
+  }
+
+  /// Subscribes once, then fails.
+  struct FailingResubscribe(AtomicU8);
+
+  #[async_trait]
+  impl SubBrokerTrait for FailingResubscribe {
+    async fn subscribe(
+      &self,
+    ) -> Result<
+      BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
+      BrokerError,
+    > {
+      if self.0.fetch_add(1, Ordering::SeqCst) > 0 {
+        return Err(BrokerError::new(MockBrokerErr));
+      }
+      Ok(stream::pending().boxed())
+    }
+  }
+
+  #[tokio::test]
+  async fn test_closed_while_retrying() {
+    let events = ConnectionEvents::default();
+    let broker =
+      Resubscriber::new(FailingResubscribe(AtomicU8::new(0)), &events)
+        .retry_delay(Duration::from_secs(3600));
+    let mut messages = broker.subscribe().await.unwrap();
+
+    events.send(ConnectionEvent::Reconnected);
+    assert!(messages.next().await.unwrap().is_err());
+
+    events.send(ConnectionEvent::Closed);
+    let next = ::tokio::time::timeout(Duration::from_secs(5), messages.next())
+      .await
+      .expect("the stream should end once the connection is closed");
+    assert!(next.is_none());
+  }
+
+  #[tokio::test]
+  async fn test_unsubscribe() {
+    let events = ConnectionEvents::default();
+    let broker = Resubscriber::new(Counter::new(false), &events);
+    let mut messages = broker.subscribe().await.unwrap();
+    messages.next().await.unwrap().unwrap();
+
+    broker.unsubscribe().await.unwrap();
+    assert!(messages.next().await.is_none());
+  }
+}
@@ -1,5 +1,6 @@
 use ::std::sync::Arc;
 
+use ::async_nats::jetstream::consumer::{OrderedPullConsumer, PullConsumer};
 use ::async_nats::jetstream::{context::Context, stream::Stream as JStream};
 use ::async_trait::async_trait;
 use ::bytes::Bytes;
@@ -12,13 +13,16 @@ use crate::traits::{AckTrait, UnSubTrait};
 use super::super::traits::SubBrokerTrait;
 
 use super::errors::NatsSubFetcherError;
-use super::options::SubFetcherOpt;
+use super::options::{BatchOpt, SubFetcherOpt, UnSubMode};
+use super::subscriptions::Subscriptions;
 
 /// Fetches pull-based JetStream messages using the configured stream options.
 #[derive(Debug)]
 pub struct SubFetcher {
+  ctx: Arc<Context>,
   stream: JStream,
   options: SubFetcherOpt,
+  subscriptions: Subscriptions,
 }
 
 impl SubFetcher {
@@ -32,7 +36,69 @@ impl SubFetcher {
     options: SubFetcherOpt,
   ) -> Result<Self, NatsSubFetcherError> {
     let stream = ctx.get_or_create_stream(options.stream_cfg.clone()).await?;
-    Ok(Self { stream, options })
+    Ok(Self {
+      ctx,
+      stream,
+      options,
+      subscriptions: Subscriptions::default(),
+    })
+  }
+
+  /// Returns the name of the consumer deleted by
+  /// [`UnSubMode::DeleteConsumer`]: its durable name, or else its name.
+  fn consumer_name(&self) -> Option<&str> {
+    if self.options.ordered {
+      return None;
+    }
+    let cfg = &self.options.pull_cfg;
+    cfg.durable_name.as_deref().or(cfg.name.as_deref())
+  }
+
+  async fn consumer(&self) -> Result<PullConsumer, BrokerError> {
+    self
+      .stream
+      .get_or_create_consumer(
+        &self.options.stream_cfg.name,
+        self.options.pull_cfg.clone(),
+      )
+      .map_err(BrokerError::from)
+      .await
+  }
+
+  /// Fetches a single batch of messages from the pull consumer.
+  ///
+  /// The returned stream ends once `batch.max_messages` messages or
+  /// `batch.max_bytes` bytes have been received, or once the batch expires.
+  ///
+  /// # Parameters
+  /// - `batch`: The limits of the batch.
+  pub async fn fetch(
+    &self,
+    batch: &BatchOpt,
+  ) -> Result<
+    BoxStream<
+      '_,
+      Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>,
+    >,
+    BrokerError,
+  > {
+    let consumer = self.consumer().await?;
+    let messages = consumer
+      .batch()
+      .max_messages(batch.max_messages)
+      .max_bytes(batch.max_bytes)
+      .expires(batch.expires)
+      .messages()
+      .map_err(BrokerError::from)
+      .await?
+      .map(|result| {
+        let (msg, acker) = result.map_err(BrokerError::from)?.split();
+        Ok((
+          msg.payload,
+          Arc::new(acker) as Arc<dyn AckTrait + Send + Sync>,
+        ))
+      });
+    Ok(messages.boxed())
   }
 }
 
@@ -40,40 +106,80 @@ impl SubFetcher {
 impl SubBrokerTrait for SubFetcher {
   /// Stream messages from the pull consumer, yielding their payloads along
   /// with the associated acknowledgment handles.
+  ///
+  /// If [`SubFetcherOpt::ordered`] is set, the messages are streamed from an
+  /// ordered consumer instead. Otherwise, if a batch is configured with
+  /// [`SubFetcherOpt::batch`], only a single batch is fetched (see
+  /// [`SubFetcher::fetch`]).
   async fn subscribe(
     &self,
   ) -> Result<
     BoxStream<Result<(Bytes, Arc<dyn AckTrait + Send + Sync>), BrokerError>>,
     BrokerError,
   > {
-    let consumer = self
-      .stream
-      .get_or_create_consumer(
-        &self.options.stream_cfg.name,
-        self.options.pull_cfg.clone(),
-      )
-      .map_err(|e| BrokerError::from(e))
-      .await?;
-    let messages = async_stream::try_stream! {
-      let mut msgs = consumer.subscribe().await?;
-      while let Some(result) = msgs.next().await {
-        yield result?;
+    let messages = if self.options.ordered {
+      let consumer: OrderedPullConsumer = self
+        .stream
+        .create_consumer(self.options.ordered_config())
+        .map_err(BrokerError::from)
+        .await?;
+      async_stream::try_stream! {
+        let mut msgs = consumer.subscribe().await?;
+        while let Some(result) = msgs.next().await {
+          yield result?;
+        }
+      }
+      .boxed()
+    } else if let Some(batch) = &self.options.batch {
+      self.fetch(batch).await?
+    } else {
+      let consumer = self.consumer().await?;
+      async_stream::try_stream! {
+        let mut msgs = consumer.subscribe().await?;
+        while let Some(result) = msgs.next().await {
+          yield result?;
+        }
       }
+      .boxed()
     };
-    Ok(messages.boxed())
+    Ok(self.subscriptions.track(messages))
   }
 }
 
 #[async_trait]
 impl UnSubTrait for SubFetcher {
-  /// Deletes the durable consumer associated with this fetcher.
+  /// Ends the streams returned by `subscribe`, then applies the
+  /// [`UnSubMode`] configured with [`SubFetcherOpt::unsub_mode`].
+  ///
+  /// Returns [`UnSubError::NothingToUnsubscribe`] if no stream returned by
+  /// `subscribe` was active and nothing was deleted, e.g. with
+  /// [`UnSubMode::Keep`], or with [`UnSubMode::DeleteConsumer`] for an
+  /// ordered consumer or a consumer with neither a durable name nor a name.
   async fn unsubscribe(&self) -> Result<(), UnSubError> {
-    if let Some(durable_name) = &self.options.pull_cfg.durable_name {
-      self
-        .stream
-        .delete_consumer(durable_name)
-        .map_err(|e| UnSubError::BrokerError(e.into()))
-        .await?;
+    let stopped = self.subscriptions.abort_all();
+    match self.options.unsub_mode {
+      UnSubMode::Keep => {}
+      UnSubMode::DeleteConsumer => {
+        if let Some(name) = self.consumer_name() {
+          self
+            .stream
+            .delete_consumer(name)
+            .map_err(|e| UnSubError::BrokerError(e.into()))
+            .await?;
+          return Ok(());
+        }
+      }
+      UnSubMode::DeleteStream => {
+        self
+          .ctx
+          .delete_stream(&self.options.stream_cfg.name)
+          .map_err(|e| UnSubError::BrokerError(e.into()))
+          .await?;
+        return Ok(());
+      }
+    }
+    if !stopped {
+      return Err(UnSubError::NothingToUnsubscribe);
     }
     Ok(())
   }
@@ -0,0 +1,121 @@
+use ::std::collections::HashMap;
+use ::std::sync::atomic::{AtomicU64, Ordering};
+use ::std::sync::{Arc, Mutex};
+
+use ::async_stream::stream;
+use ::futures::stream::{
+  AbortHandle, BoxStream, Stream, StreamExt, abortable,
+};
+
+type Handles = Arc<Mutex<HashMap<u64, AbortHandle>>>;
+
+/// Abort handles of the streams returned by a subscriber.
+///
+/// A handle is removed as soon as its stream ends or is dropped, so that
+/// subscribers called repeatedly don't accumulate the handles of finished
+/// streams.
+#[derive(Debug, Default)]
+pub(super) struct Subscriptions {
+  next_id: AtomicU64,
+  handles: Handles,
+}
+
+/// Removes the handle of a stream when dropped.
+struct Untrack {
+  id: u64,
+  handles: Handles,
+}
+
+impl Drop for Untrack {
+  fn drop(&mut self) {
+    lock(&self.handles).remove(&self.id);
+  }
+}
+
+fn lock(
+  handles: &Handles,
+) -> ::std::sync::MutexGuard<'_, HashMap<u64, AbortHandle>> {
+  handles.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Subscriptions {
+  /// Makes the stream abortable with [`Subscriptions::abort_all`], until it
+  /// ends or is dropped.
+  pub(super) fn track<'a, S>(&self, stream: S) -> BoxStream<'a, S::Item>
+  where
+    S: Stream + Send + 'a,
+    S::Item: Send,
+  {
+    let (stream, handle) = abortable(stream);
+    let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+    lock(&self.handles).insert(id, handle);
+    let untrack = Untrack {
+      id,
+      handles: self.handles.clone(),
+    };
+    stream! {
+      let _untrack = untrack;
+      for await item in stream {
+        yield item;
+      }
+    }
+    .boxed()
+  }
+
+  /// Ends the tracked streams.
+  ///
+  /// # Returns
+  /// `true` if any stream was still active
+  pub(super) fn abort_all(&self) -> bool {
+    let handles = ::std::mem::take(&mut *lock(&self.handles));
+    let active = !handles.is_empty();
+    for handle in handles.into_values() {
+      handle.abort();
+    }
+    active
+  }
+
+  #[cfg(test)]
+  fn len(&self) -> usize {
+    lock(&self.handles).len()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::futures::stream;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn test_finished_streams_are_pruned() {
+    let subscriptions = Subscriptions::default();
+
+    for _ in 0..3 {
+      let items: Vec<_> =
+        subscriptions.track(stream::iter([1, 2])).collect().await;
+      assert_eq!(items, vec![1, 2]);
+    }
+
+    assert_eq!(subscriptions.len(), 0);
+    assert!(!subscriptions.abort_all());
+  }
+
+  #[tokio::test]
+  async fn test_dropped_streams_are_pruned() {
+    let subscriptions = Subscriptions::default();
+
+    drop(subscriptions.track(stream::pending::<()>()));
+
+    assert_eq!(subscriptions.len(), 0);
+  }
+
+  #[tokio::test]
+  async fn test_abort_all() {
+    let subscriptions = Subscriptions::default();
+    let mut pending = subscriptions.track(stream::pending::<()>());
+
+    assert!(subscriptions.abort_all());
+    assert!(pending.next().await.is_none());
+  }
+}
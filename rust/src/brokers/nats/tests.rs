@@ -1,12 +1,15 @@
 use ::std::sync::Arc;
+use ::std::time::{Duration, SystemTime};
 
 use futures::StreamExt;
 use serde::{de::Error as DeErr, ser::Error as SeErr};
 
+use crate::brokers::traits::SubBrokerTrait;
 use crate::encoders::{
   Decoder as IDecoder, Encoder as IEncoder, JSONDecoder, JSONEncoder,
   MessagePackDecoder, MessagePackEncoder,
 };
+use crate::errors::UnSubError;
 use crate::options::SubOpt;
 use crate::tests::entity::TestEntity;
 use crate::{Pub, PubTrait, Sub, SubTrait, UnSubTrait};
@@ -14,21 +17,29 @@ use async_nats::jetstream::{
   consumer::pull::Config as PullConfig, stream::Config as StreamConfig,
 };
 
-use super::super::nats::{SubFetcher, SubFetcherOpt};
+use super::super::nats::{
+  Admin, BatchOpt, CoreSubscriber, KvDecoder, KvOperation, KvWatcher,
+  NatsAdminError, NatsObjectStoreError, PushSubscriber, PushSubscriberOpt,
+  SubFetcher, SubFetcherOpt, TypedObjectStore, UnSubMode,
+};
 
-async fn setup<SE: SeErr + Send + Sync, DE: DeErr + Send + Sync>(
-  name: impl Into<String>,
-  encoder: Arc<dyn IEncoder<Item = TestEntity, Error = SE> + Send + Sync>,
-  decoder: Arc<dyn IDecoder<Item = TestEntity, Error = DE> + Send + Sync>,
-) -> Option<(Pub<TestEntity, SE>, Sub<TestEntity, DE>)> {
-  let client = async_nats::connect_with_options(
+async fn connect() -> async_nats::Client {
+  async_nats::connect_with_options(
     "127.0.0.1:4222",
     async_nats::ConnectOptions::default()
       .retry_on_initial_connect()
       .max_reconnects(5),
   )
   .await
-  .unwrap();
+  .unwrap()
+}
+
+async fn setup<SE: SeErr + Send + Sync, DE: DeErr + Send + Sync>(
+  name: impl Into<String>,
+  encoder: Arc<dyn IEncoder<Item = TestEntity, Error = SE> + Send + Sync>,
+  decoder: Arc<dyn IDecoder<Item = TestEntity, Error = DE> + Send + Sync>,
+) -> Option<(Pub<TestEntity, SE>, Sub<TestEntity, DE>)> {
+  let client = connect().await;
   let js = Arc::new(async_nats::jetstream::new(client));
   let name: Arc<str> = Arc::from(format!("object_transfer_{}", name.into()));
   let publisher = Pub::new(js.clone(), name.to_string(), encoder);
@@ -88,3 +99,330 @@ async fn test_json() {
   let decoder = Arc::new(JSONDecoder::new());
   roundtrip("json", encoder, decoder).await;
 }
+
+#[tokio::test]
+async fn test_core() {
+  let client = connect().await;
+  let subject = "object_transfer_core";
+  let core = Arc::new(
+    CoreSubscriber::new(client.clone(), subject).queue_group("workers"),
+  );
+  let publisher: Pub<TestEntity, _> =
+    Pub::new(Arc::new(client), subject, Arc::new(JSONEncoder::new()));
+  let reader: Sub<TestEntity, _> = Sub::new(
+    core.clone(),
+    core,
+    Arc::new(JSONDecoder::new()),
+    SubOpt::new(),
+  );
+  let obj = TestEntity {
+    id: 42,
+    name: "Test Object".to_string(),
+  };
+
+  let mut subscriber = reader.subscribe().await.unwrap();
+  publisher.publish(&obj).await.unwrap();
+  let (recv, _) = subscriber.next().await.unwrap().unwrap();
+  reader.unsubscribe().await.unwrap();
+
+  assert_eq!(obj, recv);
+  assert!(subscriber.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_batch() {
+  let client = connect().await;
+  let js = Arc::new(async_nats::jetstream::new(client));
+  let name: Arc<str> = Arc::from("object_transfer_batch");
+  let options = SubFetcherOpt::new(name.clone())
+    .subjects(vec![name.to_string()])
+    .durable_name(name.to_string())
+    .batch(BatchOpt::new(2).expires(Duration::from_secs(1)));
+  let fetcher = Arc::new(SubFetcher::new(js.clone(), options).await.unwrap());
+  let publisher: Pub<TestEntity, _> =
+    Pub::new(js, name.to_string(), Arc::new(JSONEncoder::new()));
+  let reader: Sub<TestEntity, _> = Sub::new(
+    fetcher.clone(),
+    fetcher,
+    Arc::new(JSONDecoder::new()),
+    SubOpt::new(),
+  );
+
+  for id in 0..3 {
+    let obj = TestEntity {
+      id,
+      name: "Test Object".to_string(),
+    };
+    publisher.publish(&obj).await.unwrap();
+  }
+  let first: Vec<_> = reader.subscribe().await.unwrap().collect().await;
+  let second: Vec<_> = reader.subscribe().await.unwrap().collect().await;
+  reader.unsubscribe().await.unwrap();
+
+  assert_eq!(first.len(), 2);
+  assert_eq!(second.len(), 1);
+}
+
+#[tokio::test]
+async fn test_ordered() {
+  let client = connect().await;
+  let js = Arc::new(async_nats::jetstream::new(client));
+  let name: Arc<str> = Arc::from("object_transfer_ordered");
+  let options = SubFetcherOpt::new(name.clone())
+    .subjects(vec![name.to_string()])
+    .start_time(SystemTime::now())
+    .ordered();
+  let fetcher = Arc::new(SubFetcher::new(js.clone(), options).await.unwrap());
+  let publisher: Pub<TestEntity, _> =
+    Pub::new(js, name.to_string(), Arc::new(JSONEncoder::new()));
+  let reader: Sub<TestEntity, _> = Sub::new(
+    fetcher.clone(),
+    fetcher,
+    Arc::new(JSONDecoder::new()),
+    SubOpt::new(),
+  );
+
+  for id in 0..3 {
+    let obj = TestEntity {
+      id,
+      name: "Test Object".to_string(),
+    };
+    publisher.publish(&obj).await.unwrap();
+  }
+  let received: Vec<_> = reader
+    .subscribe()
+    .await
+    .unwrap()
+    .take(3)
+    .map(|result| result.unwrap().0.id)
+    .collect()
+    .await;
+
+  assert_eq!(received, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn test_push() {
+  let client = connect().await;
+  let js = Arc::new(async_nats::jetstream::new(client));
+  let name: Arc<str> = Arc::from("object_transfer_push");
+  let options = PushSubscriberOpt::new(name.clone(), "deliver.push")
+    .subjects(vec![name.to_string()])
+    .durable_name(name.to_string())
+    .idle_heartbeat(Duration::from_secs(5))
+    .flow_control(true);
+  let pusher =
+    Arc::new(PushSubscriber::new(js.clone(), options).await.unwrap());
+  let publisher: Pub<TestEntity, _> =
+    Pub::new(js, name.to_string(), Arc::new(JSONEncoder::new()));
+  let reader: Sub<TestEntity, _> = Sub::new(
+    pusher.clone(),
+    pusher,
+    Arc::new(JSONDecoder::new()),
+    SubOpt::new(),
+  );
+  let obj = TestEntity {
+    id: 42,
+    name: "Test Object".to_string(),
+  };
+
+  publisher.publish(&obj).await.unwrap();
+  let mut subscriber = reader.subscribe().await.unwrap();
+  let (recv, _) = subscriber.next().await.unwrap().unwrap();
+  reader.unsubscribe().await.unwrap();
+
+  assert_eq!(obj, recv);
+  assert!(subscriber.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_admin() {
+  let client = connect().await;
+  let js = Arc::new(async_nats::jetstream::new(client));
+  let name = "object_transfer_admin";
+  let _ = js.delete_stream(name).await;
+  let admin = Admin::new(js);
+  let mut cfg = StreamConfig {
+    name: name.to_string(),
+    subjects: vec![name.to_string()],
+    max_messages: 10,
+    ..Default::default()
+  };
+  let consumer = PullConfig {
+    durable_name: Some(name.to_string()),
+    ..Default::default()
+  };
+
+  let missing = admin.validate_stream(&cfg).await;
+  admin.ensure_stream(&cfg).await.unwrap();
+  let created = admin.validate_stream(&cfg).await;
+  cfg.max_messages = 20;
+  let drifted = admin.validate_stream(&cfg).await;
+  admin.ensure_stream(&cfg).await.unwrap();
+  let updated = admin.validate_stream(&cfg).await;
+  let missing_consumer = admin.validate_consumer(name, &consumer).await;
+  admin.ensure_consumer(name, &consumer).await.unwrap();
+  let created_consumer = admin.validate_consumer(name, &consumer).await;
+
+  assert!(matches!(missing, Err(NatsAdminError::StreamNotFound(_))));
+  assert!(created.is_ok());
+  assert!(matches!(
+    drifted,
+    Err(NatsAdminError::Drift(drifts))
+      if drifts.iter().any(|d| d.field == "max_msgs")
+  ));
+  assert!(updated.is_ok());
+  assert!(matches!(
+    missing_consumer,
+    Err(NatsAdminError::ConsumerNotFound(_))
+  ));
+  assert!(created_consumer.is_ok());
+}
+
+#[tokio::test]
+async fn test_kv() {
+  let client = connect().await;
+  let js = async_nats::jetstream::new(client);
+  let store = js
+    .create_key_value(async_nats::jetstream::kv::Config {
+      bucket: "object_transfer_kv".to_string(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  let watcher = Arc::new(KvWatcher::new(store.clone()).key("entity.>"));
+  let reader = Sub::new(
+    watcher.clone(),
+    watcher,
+    Arc::new(KvDecoder::new(Arc::new(JSONDecoder::<TestEntity>::new()))),
+    SubOpt::new(),
+  );
+  let publisher: Pub<TestEntity, _> = Pub::new(
+    Arc::new(store.clone()),
+    "entity.typed",
+    Arc::new(JSONEncoder::new()),
+  );
+  let typed = TestEntity {
+    id: 42,
+    name: "Typed".to_string(),
+  };
+  let raw = TestEntity {
+    id: 43,
+    name: "Raw".to_string(),
+  };
+
+  let mut subscriber = reader.subscribe().await.unwrap();
+  publisher.publish(&typed).await.unwrap();
+  store
+    .put("entity.raw", ::serde_json::to_vec(&raw).unwrap().into())
+    .await
+    .unwrap();
+  store.delete("entity.typed").await.unwrap();
+  let mut changes = Vec::new();
+  for _ in 0..3 {
+    let (change, _) = subscriber.next().await.unwrap().unwrap();
+    changes.push(change);
+  }
+  reader.unsubscribe().await.unwrap();
+
+  let summary: Vec<_> = changes
+    .iter()
+    .map(|c| (c.key.as_str(), c.operation, c.value.clone()))
+    .collect();
+  assert_eq!(
+    summary,
+    vec![
+      ("entity.typed", KvOperation::Put, Some(typed)),
+      ("entity.raw", KvOperation::Put, Some(raw)),
+      ("entity.typed", KvOperation::Delete, None),
+    ]
+  );
+  assert!(changes.windows(2).all(|w| w[0].revision < w[1].revision));
+  assert!(subscriber.next().await.is_none());
+}
+
+#[tokio::test]
+async fn test_object_store() {
+  let client = connect().await;
+  let js = async_nats::jetstream::new(client);
+  let bucket = js
+    .create_object_store(async_nats::jetstream::object_store::Config {
+      bucket: "object_transfer_objects".to_string(),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  let objects = TypedObjectStore::new(
+    bucket,
+    Arc::new(JSONEncoder::new()),
+    Arc::new(JSONDecoder::new()),
+  )
+  .chunk_size(8);
+  let obj = TestEntity {
+    id: 42,
+    name: "Test Object".to_string(),
+  };
+
+  let mut changes = objects.watch().await.unwrap();
+  let info = objects.put("entity", &obj).await.unwrap();
+  let recv = objects.get("entity").await.unwrap();
+  let put = changes.next().await.unwrap().unwrap();
+  objects.delete("entity").await.unwrap();
+  let deleted = changes.next().await.unwrap().unwrap();
+
+  assert!(info.chunks > 1);
+  assert_eq!(obj, recv);
+  assert_eq!(put.info.name, "entity");
+  assert_eq!(put.value, Some(obj));
+  assert!(deleted.info.deleted);
+  assert_eq!(deleted.value, None);
+  assert!(matches!(
+    objects.get("entity").await,
+    Err(NatsObjectStoreError::NotFound(_))
+  ));
+}
+
+#[tokio::test]
+async fn test_unsub_modes() {
+  let client = connect().await;
+  let js = Arc::new(async_nats::jetstream::new(client));
+  let name: Arc<str> = Arc::from("object_transfer_unsub");
+  let keep = SubFetcher::new(
+    js.clone(),
+    SubFetcherOpt::new(name.clone()).unsub_mode(UnSubMode::Keep),
+  )
+  .await
+  .unwrap();
+  let temp = SubFetcher::new(
+    js.clone(),
+    SubFetcherOpt::new(name.clone()).unsub_mode(UnSubMode::DeleteStream),
+  )
+  .await
+  .unwrap();
+
+  assert!(matches!(
+    keep.unsubscribe().await,
+    Err(UnSubError::NothingToUnsubscribe)
+  ));
+  let _messages = keep.subscribe().await.unwrap();
+  keep.unsubscribe().await.unwrap();
+  temp.unsubscribe().await.unwrap();
+
+  assert!(js.get_stream(name.as_ref()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_push_unsubscribe_deletes_named_consumer() {
+  let client = connect().await;
+  let js = Arc::new(async_nats::jetstream::new(client));
+  let name: Arc<str> = Arc::from("object_transfer_push_unsub");
+  let options = PushSubscriberOpt::new(name.clone(), "deliver.push_unsub")
+    .subjects(vec![name.to_string()]);
+  let pusher = PushSubscriber::new(js.clone(), options).await.unwrap();
+
+  let _messages = pusher.subscribe().await.unwrap();
+  pusher.unsubscribe().await.unwrap();
+
+  let stream = js.get_stream(name.as_ref()).await.unwrap();
+  assert!(stream.consumer_info(name.as_ref()).await.is_err());
+}
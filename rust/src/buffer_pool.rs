@@ -0,0 +1,208 @@
+//! Pool of reusable encoding buffers.
+//!
+//! Encoding into a freshly allocated buffer per message is the main
+//! allocation cost of high-rate publishers. A [`BufferPool`] hands out
+//! [`BytesMut`] buffers that [`Pub`](crate::Pub) encodes into with
+//! [`Encoder::encode_into`](crate::encoders::Encoder::encode_into). The
+//! published payload is split off the buffer, and the buffer goes back to
+//! the pool; once the payload is dropped by the broker, the next write to the
+//! buffer reclaims its memory instead of allocating.
+
+use ::std::sync::Mutex;
+
+use ::bytes::BytesMut;
+
+/// Default capacity of the buffers allocated by the pool.
+const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+
+/// Default maximum number of idle buffers kept by the pool.
+const DEFAULT_MAX_BUFFERS: usize = 64;
+
+/// Default maximum capacity of the buffers kept by the pool.
+const DEFAULT_MAX_BUFFER_CAPACITY: usize = 1024 * 1024;
+
+/// Pool of reusable encoding buffers.
+///
+/// The pool can be shared by several publishers.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use serde::Serialize;
+/// use object_transfer::{BufferPool, Pub, traits::PubTrait};
+/// use object_transfer::encoders::JSONEncoder;
+///
+/// #[derive(Serialize)]
+/// struct Tick {
+///   price: f64,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///   let client = async_nats::connect("demo.nats.io").await?;
+///   let js = Arc::new(async_nats::jetstream::new(client));
+///   let pool = Arc::new(BufferPool::new().buffer_capacity(256));
+///
+///   let publisher: Pub<Tick, _> =
+///     Pub::new(js, "ticks", Arc::new(JSONEncoder::new())).buffer_pool(pool);
+///
+///   publisher.publish(&Tick { price: 1.0 }).await?;
+///   Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BufferPool {
+  buffers: Mutex<Vec<BytesMut>>,
+  buffer_capacity: usize,
+  max_buffers: usize,
+  max_buffer_capacity: usize,
+}
+
+impl BufferPool {
+  /// Creates an empty pool allocating 8 KiB buffers and keeping up to 64
+  /// idle buffers of at most 1 MiB.
+  pub fn new() -> Self {
+    Self {
+      buffers: Mutex::new(Vec::new()),
+      buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+      max_buffers: DEFAULT_MAX_BUFFERS,
+      max_buffer_capacity: DEFAULT_MAX_BUFFER_CAPACITY,
+    }
+  }
+
+  /// Sets the capacity of the buffers allocated by the pool.
+  ///
+  /// # Parameters
+  /// - `capacity`: The capacity in bytes. Buffers grow if a message doesn't
+  ///   fit.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn buffer_capacity(mut self, capacity: usize) -> Self {
+    self.buffer_capacity = capacity;
+    self
+  }
+
+  /// Sets the maximum number of idle buffers kept by the pool.
+  ///
+  /// # Parameters
+  /// - `max_buffers`: Buffers released while the pool is full are dropped.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn max_buffers(mut self, max_buffers: usize) -> Self {
+    self.max_buffers = max_buffers;
+    self
+  }
+
+  /// Sets the maximum capacity of the buffers kept by the pool.
+  ///
+  /// # Parameters
+  /// - `capacity`: The capacity in bytes. Buffers that grew beyond it while
+  ///   encoding a large message are dropped when released, so that they
+  ///   don't hold their memory for the lifetime of the pool.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn max_buffer_capacity(mut self, capacity: usize) -> Self {
+    self.max_buffer_capacity = capacity;
+    self
+  }
+
+  /// Takes an empty buffer from the pool, or allocates one if the pool is
+  /// empty.
+  pub fn acquire(&self) -> BytesMut {
+    self
+      .buffers
+      .lock()
+      .unwrap_or_else(|e| e.into_inner())
+      .pop()
+      .unwrap_or_else(|| BytesMut::with_capacity(self.buffer_capacity))
+  }
+
+  /// Returns a buffer to the pool.
+  ///
+  /// # Parameters
+  /// - `buf`: The buffer. Its content is discarded, and the buffer is
+  ///   dropped if its capacity exceeds the maximum buffer capacity.
+  pub fn release(&self, mut buf: BytesMut) {
+    if buf.capacity() > self.max_buffer_capacity {
+      return;
+    }
+    buf.clear();
+    let mut buffers = self.buffers.lock().unwrap_or_else(|e| e.into_inner());
+    if buffers.len() < self.max_buffers {
+      buffers.push(buf);
+    }
+  }
+
+  /// Returns the number of idle buffers in the pool.
+  pub fn len(&self) -> usize {
+    self.buffers.lock().unwrap_or_else(|e| e.into_inner()).len()
+  }
+
+  /// Returns `true` if the pool has no idle buffer.
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+impl Default for BufferPool {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::bytes::BufMut;
+
+  use super::*;
+
+  #[test]
+  fn test_acquire_allocates_with_capacity() {
+    let pool = BufferPool::new().buffer_capacity(128);
+
+    let buf = pool.acquire();
+
+    assert!(buf.capacity() >= 128);
+    assert!(buf.is_empty());
+  }
+
+  #[test]
+  fn test_released_buffer_is_reused() {
+    let pool = BufferPool::new().buffer_capacity(128);
+    let mut buf = pool.acquire();
+    buf.put_slice(b"payload");
+    let ptr = buf.as_ptr();
+
+    pool.release(buf);
+    let buf = pool.acquire();
+
+    assert_eq!(buf.as_ptr(), ptr);
+    assert!(buf.is_empty());
+    assert!(pool.is_empty());
+  }
+
+  #[test]
+  fn test_max_buffers() {
+    let pool = BufferPool::new().max_buffers(1);
+
+    pool.release(BytesMut::new());
+    pool.release(BytesMut::new());
+
+    assert_eq!(pool.len(), 1);
+  }
+
+  #[test]
+  fn test_oversized_buffer_is_dropped() {
+    let pool = BufferPool::new().max_buffer_capacity(1024);
+
+    pool.release(BytesMut::with_capacity(4096));
+    pool.release(BytesMut::with_capacity(512));
+
+    assert_eq!(pool.len(), 1);
+    assert!(pool.acquire().capacity() <= 1024);
+  }
+}
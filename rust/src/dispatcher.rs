@@ -0,0 +1,425 @@
+//! Routing of type-tagged items to per-type async handlers.
+//!
+//! The [`Dispatcher`] complements the
+//! [type-tagged envelope codec](crate::encoders::tagged): it holds one async
+//! handler per type tag and invokes the handler matching the
+//! [`TypeTag`] of each item. Consuming a subscriber through
+//! [`Dispatcher::run`] acknowledges each message once its handler succeeded,
+//! and reports payloads of unregistered types as
+//! [`DispatchError::UnknownType`], acknowledging them as well since no retry
+//! can succeed.
+
+use ::std::collections::HashMap;
+use ::std::error::Error as StdError;
+use ::std::future::Future;
+
+use ::futures::future::BoxFuture;
+use ::futures::stream::{BoxStream, StreamExt};
+use ::futures::{FutureExt, TryFutureExt};
+
+use crate::encoders::tagged::{TaggedError, TypeTag};
+use crate::errors::DispatchError;
+use crate::traits::SubTrait;
+
+type HandlerFn<T> = Box<
+  dyn Fn(T) -> BoxFuture<'static, Result<(), Box<dyn StdError + Send + Sync>>>
+    + Send
+    + Sync,
+>;
+
+/// Dispatcher routing items to the handler registered for their type tag.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use object_transfer::Dispatcher;
+/// use object_transfer::encoders::tagged::{TaggedError, TypeTag};
+/// use object_transfer::traits::SubTrait;
+///
+/// enum Event {
+///   Created(u32),
+///   Deleted(u32),
+/// }
+///
+/// impl TypeTag for Event {
+///   fn type_tag(&self) -> &str {
+///     match self {
+///       Event::Created(_) => "created",
+///       Event::Deleted(_) => "deleted",
+///     }
+///   }
+/// }
+///
+/// async fn consume<S>(sub: &S) -> Result<(), Box<dyn std::error::Error>>
+/// where
+///   S: SubTrait<Item = Event, DecodeErr = TaggedError> + Send + Sync,
+/// {
+///   let dispatcher = Dispatcher::new()
+///     .on("created", async |event: Event| {
+///       println!("created");
+///       Ok::<_, std::io::Error>(())
+///     })
+///     .on("deleted", async |event: Event| {
+///       println!("deleted");
+///       Ok::<_, std::io::Error>(())
+///     });
+///   let mut results = dispatcher.run(sub).await?;
+///   while let Some(result) = results.next().await {
+///     if let Err(e) = result {
+///       eprintln!("dispatch failed: {e}");
+///     }
+///   }
+///   Ok(())
+/// }
+/// ```
+pub struct Dispatcher<T: TypeTag> {
+  handlers: HashMap<String, HandlerFn<T>>,
+  ack_unknown: bool,
+}
+
+impl<T: TypeTag + Send + Sync + 'static> Dispatcher<T> {
+  /// Creates a dispatcher without any registered handler.
+  pub fn new() -> Self {
+    Self {
+      handlers: HashMap::new(),
+      ack_unknown: true,
+    }
+  }
+
+  /// Sets whether [`Dispatcher::run`] acknowledges items no handler is
+  /// registered for. Defaults to `true`, since redelivering them can't
+  /// succeed.
+  ///
+  /// # Parameters
+  /// - `ack_unknown`: If false, unknown items are left unacknowledged so
+  ///   that the broker redelivers them, e.g. until a new handler is
+  ///   deployed.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn ack_unknown(mut self, ack_unknown: bool) -> Self {
+    self.ack_unknown = ack_unknown;
+    self
+  }
+
+  /// Registers the handler for items tagged `type_tag`.
+  ///
+  /// Registering the same type tag twice replaces the previous handler.
+  ///
+  /// # Parameters
+  /// - `type_tag`: Type tag of the handled items.
+  /// - `handler`: Async function processing the items.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn on<F, Fut, E>(
+    mut self,
+    type_tag: impl Into<String>,
+    handler: F,
+  ) -> Self
+  where
+    F: Fn(T) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: StdError + Send + Sync + 'static,
+  {
+    let handler: HandlerFn<T> = Box::new(move |item| {
+      handler(item)
+        .map_err(|e| Box::new(e) as Box<dyn StdError + Send + Sync>)
+        .boxed()
+    });
+    self.handlers.insert(type_tag.into(), handler);
+    self
+  }
+
+  /// Invokes the handler registered for the type tag of `item`.
+  ///
+  /// # Parameters
+  /// - `item`: The item to dispatch.
+  pub async fn dispatch(&self, item: T) -> Result<(), DispatchError> {
+    let type_tag = item.type_tag().to_string();
+    let handler = self
+      .handlers
+      .get(&type_tag)
+      .ok_or_else(|| DispatchError::UnknownType(type_tag.clone()))?;
+    handler(item)
+      .await
+      .map_err(|source| DispatchError::HandlerError { type_tag, source })
+  }
+
+  /// Subscribes and dispatches every received item.
+  ///
+  /// Each message is acknowledged after its handler succeeded, so the
+  /// subscriber should be created with auto-acknowledgment disabled. Messages
+  /// whose handler failed are left unacknowledged so that the broker
+  /// redelivers them, while items without a handler are acknowledged (see
+  /// [`Dispatcher::ack_unknown`]) and reported as
+  /// [`DispatchError::UnknownType`].
+  ///
+  /// Payloads whose type tag the decoder doesn't know fail before reaching
+  /// the dispatcher; create the subscriber with
+  /// [`SubOpt::ack_undecodable`](crate::SubOpt::ack_undecodable) to have
+  /// them acknowledged too.
+  ///
+  /// # Parameters
+  /// - `sub`: Subscriber decoding type-tagged envelopes.
+  ///
+  /// # Returns
+  /// A stream yielding the dispatch result of each message.
+  pub async fn run<'a, S>(
+    &'a self,
+    sub: &'a S,
+  ) -> Result<BoxStream<'a, Result<(), DispatchError>>, DispatchError>
+  where
+    S: SubTrait<Item = T, DecodeErr = TaggedError> + Send + Sync,
+  {
+    let messages = sub.subscribe().await?;
+    let results = messages.then(async move |msg| {
+      let (item, ack) = msg?;
+      match self.dispatch(item).await {
+        Err(DispatchError::UnknownType(type_tag)) if self.ack_unknown => {
+          ack.ack().await?;
+          Err(DispatchError::UnknownType(type_tag))
+        }
+        result => {
+          result?;
+          ack.ack().await?;
+          Ok(())
+        }
+      }
+    });
+    Ok(results.boxed())
+  }
+}
+
+impl<T: TypeTag + Send + Sync + 'static> Default for Dispatcher<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+  use ::std::sync::{Arc, Mutex};
+
+  use ::bytes::Bytes;
+  use ::futures::stream::TryStreamExt;
+
+  use crate::brokers::SubBrokerTrait;
+  use crate::encoders::tagged::{Decoder, Encoder};
+  use crate::encoders::{Encoder as _, JSONDecoder, JSONEncoder};
+  use crate::tests::entity::TestEntity;
+  use crate::tests::subscribe::SubscribeMock;
+  use crate::traits::{AckTrait, MockAckTrait};
+  use crate::{Sub, SubOpt, UnSubNoop};
+
+  use super::*;
+
+  #[derive(Debug, Clone, PartialEq)]
+  enum Event {
+    Created(TestEntity),
+    Deleted(u32),
+  }
+
+  impl TypeTag for Event {
+    fn type_tag(&self) -> &str {
+      match self {
+        Event::Created(_) => "created",
+        Event::Deleted(_) => "deleted",
+      }
+    }
+  }
+
+  #[derive(Debug, thiserror::Error)]
+  #[error("Handler Test Error")]
+  struct HandlerErr;
+
+  fn encoder() -> Encoder<Event> {
+    Encoder::new()
+      .variant(
+        "created",
+        Arc::new(JSONEncoder::new()),
+        |e: &Event| match e {
+          Event::Created(v) => Some(v),
+          _ => None,
+        },
+      )
+      .variant(
+        "deleted",
+        Arc::new(JSONEncoder::new()),
+        |e: &Event| match e {
+          Event::Deleted(v) => Some(v),
+          _ => None,
+        },
+      )
+  }
+
+  fn ack(times: usize) -> Arc<dyn AckTrait + Send + Sync> {
+    let mut ack = MockAckTrait::new();
+    ack.expect_ack().returning(|| Ok(())).times(times);
+    Arc::new(ack)
+  }
+
+  fn sub(
+    data: Vec<(Bytes, Arc<dyn AckTrait + Send + Sync>)>,
+  ) -> Sub<Event, TaggedError> {
+    sub_with(data, SubOpt::new().auto_ack(false))
+  }
+
+  fn sub_with(
+    data: Vec<(Bytes, Arc<dyn AckTrait + Send + Sync>)>,
+    options: SubOpt,
+  ) -> Sub<Event, TaggedError> {
+    let ctx: Arc<dyn SubBrokerTrait + Send + Sync> =
+      Arc::new(SubscribeMock::new(data));
+    let decoder = Decoder::new()
+      .variant("created", Arc::new(JSONDecoder::new()), Event::Created)
+      .variant("deleted", Arc::new(JSONDecoder::new()), Event::Deleted);
+    Sub::new(
+      ctx,
+      Arc::new(UnSubNoop::new(false)),
+      Arc::new(decoder),
+      options,
+    )
+  }
+
+  fn recording_dispatcher(
+    received: Arc<Mutex<Vec<Event>>>,
+  ) -> Dispatcher<Event> {
+    let created = received.clone();
+    let deleted = received;
+    Dispatcher::new()
+      .on("created", move |event: Event| {
+        created.lock().unwrap().push(event);
+        async { Ok::<_, HandlerErr>(()) }
+      })
+      .on("deleted", move |event: Event| {
+        deleted.lock().unwrap().push(event);
+        async { Ok::<_, HandlerErr>(()) }
+      })
+  }
+
+  #[tokio::test]
+  async fn test_run_routes_and_acks() {
+    let events = vec![
+      Event::Created(TestEntity::new(1, "created")),
+      Event::Deleted(1),
+    ];
+    let encoder = encoder();
+    let data = events
+      .iter()
+      .map(|e| (encoder.encode(e).unwrap(), ack(1)))
+      .collect();
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let dispatcher = recording_dispatcher(received.clone());
+    let sub = sub(data);
+
+    dispatcher
+      .run(&sub)
+      .await
+      .unwrap()
+      .try_collect::<Vec<_>>()
+      .await
+      .unwrap();
+
+    assert_eq!(*received.lock().unwrap(), events);
+  }
+
+  #[tokio::test]
+  async fn test_run_unknown_type() {
+    let data = vec![(Bytes::from_static(b"\x07renamed1"), ack(0))];
+    let dispatcher = recording_dispatcher(Arc::new(Mutex::new(Vec::new())));
+    let sub = sub(data);
+
+    let results: Vec<_> = dispatcher.run(&sub).await.unwrap().collect().await;
+
+    assert!(matches!(
+      &results[..],
+      [Err(DispatchError::UnknownType(tag))] if tag == "renamed"
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_run_acks_undecodable_unknown_type() {
+    let data = vec![(Bytes::from_static(b"\x07renamed1"), ack(1))];
+    let dispatcher = recording_dispatcher(Arc::new(Mutex::new(Vec::new())));
+    let sub =
+      sub_with(data, SubOpt::new().auto_ack(false).ack_undecodable(true));
+
+    let results: Vec<_> = dispatcher.run(&sub).await.unwrap().collect().await;
+
+    assert!(matches!(
+      &results[..],
+      [Err(DispatchError::UnknownType(tag))] if tag == "renamed"
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_run_acks_unhandled_type() {
+    let data = vec![(encoder().encode(&Event::Deleted(1)).unwrap(), ack(1))];
+    let dispatcher = Dispatcher::new()
+      .on("created", async |_: Event| Ok::<_, HandlerErr>(()));
+    let sub = sub(data);
+
+    let results: Vec<_> = dispatcher.run(&sub).await.unwrap().collect().await;
+
+    assert!(matches!(
+      &results[..],
+      [Err(DispatchError::UnknownType(tag))] if tag == "deleted"
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_run_keeps_unhandled_type() {
+    let data = vec![(encoder().encode(&Event::Deleted(1)).unwrap(), ack(0))];
+    let dispatcher = Dispatcher::new()
+      .ack_unknown(false)
+      .on("created", async |_: Event| Ok::<_, HandlerErr>(()));
+    let sub = sub(data);
+
+    let results: Vec<_> = dispatcher.run(&sub).await.unwrap().collect().await;
+
+    assert!(matches!(
+      &results[..],
+      [Err(DispatchError::UnknownType(tag))] if tag == "deleted"
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_run_decode_error() {
+    let data = vec![(Bytes::from_static(b"\x07deleted{"), ack(0))];
+    let dispatcher = recording_dispatcher(Arc::new(Mutex::new(Vec::new())));
+    let sub = sub(data);
+
+    let results: Vec<_> = dispatcher.run(&sub).await.unwrap().collect().await;
+
+    assert!(matches!(&results[..], [Err(DispatchError::SubError(_))]));
+  }
+
+  #[tokio::test]
+  async fn test_handler_error_is_not_acked() {
+    let data = vec![(encoder().encode(&Event::Deleted(1)).unwrap(), ack(0))];
+    let dispatcher = Dispatcher::new()
+      .on("deleted", async |_: Event| Err::<(), _>(HandlerErr));
+    let sub = sub(data);
+
+    let results: Vec<_> = dispatcher.run(&sub).await.unwrap().collect().await;
+
+    assert!(matches!(
+      &results[..],
+      [Err(DispatchError::HandlerError { type_tag, .. })] if type_tag == "deleted"
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_dispatch_without_handler() {
+    let dispatcher: Dispatcher<Event> = Dispatcher::new();
+
+    let result = dispatcher.dispatch(Event::Deleted(1)).await;
+
+    assert!(matches!(
+      result,
+      Err(DispatchError::UnknownType(tag)) if tag == "deleted"
+    ));
+  }
+}
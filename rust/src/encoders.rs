@@ -22,12 +22,57 @@
 //!
 //! - **JSON** (feature `json`) - Human-readable, widely compatible, available via [`json`] module
 //! - **MessagePack** (feature `msgpack`) - Compact binary format, faster than JSON, available via [`msgpack`] module
+//! - **Protocol Buffers** (feature `protobuf`) - Schema-based binary format for [`prost::Message`] types,
+//!   available via [`protobuf`] module
+//! - **Bincode** (feature `bincode`) - Fast, compact binary format for Rust-to-Rust traffic,
+//!   available via [`bincode`] module
+//! - **Postcard** (feature `postcard`) - Smallest binary format with variable-length integers,
+//!   available via [`postcard`] module
+//! - **Avro** (feature `avro`) - Schema registry wire format with reader-schema evolution,
+//!   available via [`avro`] module
+//! - **CloudEvents** (feature `cloudevents`) - CloudEvents 1.0 structured JSON envelope around any
+//!   inner encoder, available via [`cloudevents`] module
+//! - **JSON Schema** (feature `jsonschema`) - JSON decoder validating payloads against a JSON Schema,
+//!   available via [`json_schema`] module
+//!
+//! # Raw Payloads
+//!
+//! The [`raw`] module publishes `Bytes`, `Vec<u8>` and `String` items as-is,
+//! bypassing serde, for bridges and tooling moving opaque payloads.
+//!
+//! # Config-Driven Format Selection
+//!
+//! The [`registry`] module maps format names such as `"json"` to encoder and
+//! decoder factories, so that the format can be read from configuration.
+//!
+//! # Mixed Formats
+//!
+//! The [`multi_format`] module provides an encoder tagging payloads with their
+//! content type and a decoder dispatching to one of several registered
+//! decoders, which helps migrating a topic from one format to another.
+//!
+//! # Schema Evolution
+//!
+//! The [`versioned`] module prefixes payloads with a schema version and
+//! upcasts payloads of old versions to the latest type while decoding.
+//!
+//! # Polymorphic Topics
+//!
+//! The [`tagged`] module wraps payloads in an envelope carrying a type tag,
+//! so that one topic can carry many event kinds decoded into a single enum.
+//! See [`Dispatcher`](crate::Dispatcher) to route them to per-type handlers.
+//!
+//! # Zero-Copy Decoding
+//!
+//! The [`borrowed`] module (feature `borrowed`) provides JSON and MessagePack
+//! decoders yielding guards that keep the payload alive alongside an item
+//! borrowing its string fields from it.
 //!
 //! # Custom Formats
 //!
 //! You are not limited to built-in formats. Implement [`Encoder`] and [`Decoder`] for any
 //! serialization format you need:
-//! - Other binary formats: CBOR, Protocol Buffers, Avro, Parquet
+//! - Other binary formats: CBOR, Parquet
 //! - Custom formats: domain-specific binary protocols, compressed formats
 //! - Specialized formats: for specific use cases or performance requirements
 //!
@@ -35,6 +80,13 @@
 //!
 //! - `json` - Enables JSON encoding/decoding support
 //! - `msgpack` - Enables MessagePack encoding/decoding support
+//! - `protobuf` - Enables Protocol Buffers encoding/decoding support
+//! - `bincode` - Enables bincode encoding/decoding support
+//! - `postcard` - Enables postcard encoding/decoding support
+//! - `avro` - Enables Apache Avro encoding/decoding support
+//! - `cloudevents` - Enables CloudEvents encoding/decoding support (implies `json`)
+//! - `jsonschema` - Enables the JSON Schema validating JSON decoder (implies `json`)
+//! - `borrowed` - Enables the zero-copy decoders of the enabled formats
 //!
 //! # Examples
 //!
@@ -64,9 +116,30 @@
 //! # }
 //! ```
 
+#[cfg(any(
+  feature = "json",
+  feature = "msgpack",
+  feature = "bincode",
+  feature = "postcard"
+))]
+mod buf_writer;
+pub mod multi_format;
+pub mod raw;
+pub mod registry;
+pub mod tagged;
 mod traits;
+pub mod versioned;
 
+pub use self::multi_format::{
+  Decoder as MultiFormatDecoder, Encoder as MultiFormatEncoder,
+};
+pub use self::raw::{Decoder as RawDecoder, Encoder as RawEncoder};
+pub use self::registry::{CodecError, CodecRegistry, CodecRegistryError};
+pub use self::tagged::{Decoder as TaggedDecoder, Encoder as TaggedEncoder};
 pub use self::traits::{Decoder, Encoder};
+pub use self::versioned::{
+  Decoder as VersionedDecoder, Encoder as VersionedEncoder,
+};
 
 #[cfg(test)]
 pub use self::traits::{MockDecoder, MockEncoder};
@@ -82,3 +155,44 @@ pub mod msgpack;
 pub use self::msgpack::{
   Decoder as MessagePackDecoder, Encoder as MessagePackEncoder,
 };
+
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+#[cfg(feature = "protobuf")]
+pub use self::protobuf::{
+  Decoder as ProtobufDecoder, Encoder as ProtobufEncoder,
+};
+
+#[cfg(feature = "bincode")]
+pub mod bincode;
+#[cfg(feature = "bincode")]
+pub use self::bincode::{
+  Decoder as BincodeDecoder, Encoder as BincodeEncoder,
+};
+
+#[cfg(feature = "postcard")]
+pub mod postcard;
+#[cfg(feature = "postcard")]
+pub use self::postcard::{
+  Decoder as PostcardDecoder, Encoder as PostcardEncoder,
+};
+
+#[cfg(feature = "avro")]
+pub mod avro;
+#[cfg(feature = "avro")]
+pub use self::avro::{Decoder as AvroDecoder, Encoder as AvroEncoder};
+
+#[cfg(feature = "cloudevents")]
+pub mod cloudevents;
+#[cfg(feature = "cloudevents")]
+pub use self::cloudevents::{
+  Decoder as CloudEventsDecoder, Encoder as CloudEventsEncoder,
+};
+
+#[cfg(feature = "borrowed")]
+pub mod borrowed;
+
+#[cfg(feature = "jsonschema")]
+pub mod json_schema;
+#[cfg(feature = "jsonschema")]
+pub use self::json_schema::Decoder as JSONSchemaDecoder;
@@ -0,0 +1,179 @@
+use ::std::collections::HashMap;
+use ::std::marker::PhantomData;
+use ::std::sync::{Arc, RwLock};
+
+use ::apache_avro::{
+  Schema, from_avro_datum, from_value, to_avro_datum, to_value,
+};
+use ::bytes::{BufMut, Bytes, BytesMut};
+use ::serde::{de::DeserializeOwned, ser::Serialize};
+
+use super::super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+use super::errors::AvroError;
+use super::registry::SchemaRegistry;
+
+/// Magic byte that starts every payload in the registry wire format.
+const MAGIC_BYTE: u8 = 0;
+/// Length of the magic byte and the schema id.
+const HEADER_LEN: usize = 5;
+
+/// An Avro encoder writing the schema registry wire format.
+///
+/// The writer schema is registered when the encoder is created, and its id
+/// is prepended to every encoded datum.
+///
+/// # Type Parameters
+///
+/// * `T` - The data type to be encoded. Must implement [`Serialize`],
+///   [`Send`], and [`Sync`], and its serialized form must match the writer
+///   schema.
+pub struct Encoder<T: Serialize + Send + Sync> {
+  schema: Arc<Schema>,
+  schema_id: u32,
+  _marker: PhantomData<T>,
+}
+
+impl<T: Serialize + Send + Sync> Encoder<T> {
+  /// Creates a new encoder, registering `schema` under `subject`.
+  ///
+  /// # Parameters
+  /// - `registry`: Registry used to obtain the schema id.
+  /// - `subject`: Registry subject the schema is registered under.
+  /// - `schema`: Writer schema used to encode the items.
+  pub fn new(
+    registry: Arc<dyn SchemaRegistry + Send + Sync>,
+    subject: &str,
+    schema: Schema,
+  ) -> Result<Self, AvroError> {
+    let schema_id = registry.register(subject, &schema)?;
+    Ok(Self {
+      schema: Arc::new(schema),
+      schema_id,
+      _marker: PhantomData,
+    })
+  }
+
+  /// Creates a new encoder using a schema that is already registered.
+  ///
+  /// # Parameters
+  /// - `registry`: Registry used to look up the schema.
+  /// - `schema_id`: Id of the writer schema.
+  pub fn from_id(
+    registry: Arc<dyn SchemaRegistry + Send + Sync>,
+    schema_id: u32,
+  ) -> Result<Self, AvroError> {
+    let schema = registry.schema(schema_id)?;
+    Ok(Self {
+      schema,
+      schema_id,
+      _marker: PhantomData,
+    })
+  }
+
+  /// Returns the registry id of the writer schema.
+  pub fn schema_id(&self) -> u32 {
+    self.schema_id
+  }
+}
+
+impl<T: Serialize + Send + Sync> EncoderTrait for Encoder<T> {
+  type Item = T;
+  type Error = AvroError;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    let datum = to_avro_datum(&self.schema, to_value(item)?)?;
+    let mut buf = BytesMut::with_capacity(HEADER_LEN + datum.len());
+    buf.put_u8(MAGIC_BYTE);
+    buf.put_u32(self.schema_id);
+    buf.put_slice(&datum);
+    Ok(buf.freeze())
+  }
+}
+
+/// An Avro decoder reading the schema registry wire format.
+///
+/// The writer schema is looked up in the registry by the id found in each
+/// payload and cached for subsequent messages. When a reader schema is set,
+/// the datum is resolved against it following the Avro schema resolution
+/// rules, so fields added with defaults or removed on the reader side are
+/// handled transparently.
+///
+/// # Type Parameters
+///
+/// * `T` - The data type to be decoded. Must implement [`DeserializeOwned`],
+///   [`Send`], and [`Sync`].
+pub struct Decoder<T: DeserializeOwned + Send + Sync> {
+  registry: Arc<dyn SchemaRegistry + Send + Sync>,
+  reader_schema: Option<Schema>,
+  writer_schemas: RwLock<HashMap<u32, Arc<Schema>>>,
+  _marker: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned + Send + Sync> Decoder<T> {
+  /// Creates a new decoder that decodes with the writer schema only.
+  ///
+  /// # Parameters
+  /// - `registry`: Registry used to look up writer schemas.
+  pub fn new(registry: Arc<dyn SchemaRegistry + Send + Sync>) -> Self {
+    Self {
+      registry,
+      reader_schema: None,
+      writer_schemas: RwLock::new(HashMap::new()),
+      _marker: PhantomData,
+    }
+  }
+
+  /// Sets the reader schema that decoded data is resolved against.
+  ///
+  /// # Parameters
+  /// - `schema`: Reader schema describing `T`.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn reader_schema(mut self, schema: Schema) -> Self {
+    self.reader_schema = Some(schema);
+    self
+  }
+
+  fn writer_schema(&self, id: u32) -> Result<Arc<Schema>, AvroError> {
+    let cached = self
+      .writer_schemas
+      .read()
+      .unwrap_or_else(|e| e.into_inner())
+      .get(&id)
+      .cloned();
+    if let Some(schema) = cached {
+      return Ok(schema);
+    }
+    let schema = self.registry.schema(id)?;
+    self
+      .writer_schemas
+      .write()
+      .unwrap_or_else(|e| e.into_inner())
+      .insert(id, schema.clone());
+    Ok(schema)
+  }
+}
+
+impl<T: DeserializeOwned + Send + Sync> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = AvroError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    if data.len() < HEADER_LEN {
+      return Err(AvroError::TruncatedHeader(data.len()));
+    }
+    if data[0] != MAGIC_BYTE {
+      return Err(AvroError::InvalidMagicByte(data[0]));
+    }
+    let id = u32::from_be_bytes([data[1], data[2], data[3], data[4]]);
+    let writer_schema = self.writer_schema(id)?;
+    let mut datum = &data[HEADER_LEN..];
+    let value = from_avro_datum(
+      &writer_schema,
+      &mut datum,
+      self.reader_schema.as_ref(),
+    )?;
+    Ok(from_value(&value)?)
+  }
+}
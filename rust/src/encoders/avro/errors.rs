@@ -0,0 +1,42 @@
+use ::std::error::Error as StdError;
+
+use ::thiserror::Error;
+
+/// Errors reported by a [`SchemaRegistry`](super::SchemaRegistry).
+#[derive(Error, Debug)]
+pub enum RegistryError {
+  /// No schema is registered under the requested id.
+  #[error("Schema {0} not found")]
+  NotFound(u32),
+  /// The registry backend (e.g. an HTTP client) failed.
+  #[error("Schema registry backend error: {0}")]
+  Backend(Box<dyn StdError + Send + Sync>),
+}
+
+impl RegistryError {
+  /// Wraps an error raised by a registry backend.
+  pub fn backend<E>(err: E) -> Self
+  where
+    E: StdError + Send + Sync + 'static,
+  {
+    Self::Backend(Box::new(err))
+  }
+}
+
+/// Error type for Avro encoding and decoding.
+#[derive(Error, Debug)]
+pub enum AvroError {
+  /// The datum could not be serialized, deserialized or resolved against
+  /// the schema.
+  #[error("Avro error: {0}")]
+  Avro(#[from] ::apache_avro::Error),
+  /// The writer schema could not be registered or looked up.
+  #[error("Schema registry error: {0}")]
+  Registry(#[from] RegistryError),
+  /// The payload doesn't start with the wire format magic byte.
+  #[error("Unknown magic byte: {0:#04x}")]
+  InvalidMagicByte(u8),
+  /// The payload is shorter than the wire format header.
+  #[error("Payload too short for the wire format header: {0} bytes")]
+  TruncatedHeader(usize),
+}
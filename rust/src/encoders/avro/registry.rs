@@ -0,0 +1,101 @@
+use ::std::collections::HashMap;
+use ::std::sync::{Arc, RwLock};
+
+use ::apache_avro::Schema;
+
+use super::errors::RegistryError;
+
+/// Resolves Avro schemas to ids and back.
+///
+/// Implement this trait to plug in a schema registry client, e.g. one that
+/// talks to a Confluent-compatible registry over HTTP. The methods are
+/// synchronous because they are called from [`Encoder`](crate::encoders::Encoder)
+/// and [`Decoder`](crate::encoders::Decoder), which are synchronous as well.
+/// Note that the Avro codecs only call [`register`](Self::register) once at
+/// construction time and cache the schemas returned by
+/// [`schema`](Self::schema), so blocking implementations are acceptable.
+pub trait SchemaRegistry {
+  /// Registers `schema` under `subject` and returns its id.
+  ///
+  /// Registering a schema that is already known must return the existing
+  /// id instead of creating a new one.
+  ///
+  /// # Parameters
+  /// - `subject`: Registry subject (e.g. `"<topic>-value"`).
+  /// - `schema`: Writer schema to register.
+  fn register(
+    &self,
+    subject: &str,
+    schema: &Schema,
+  ) -> Result<u32, RegistryError>;
+
+  /// Returns the schema registered under `id`.
+  ///
+  /// # Parameters
+  /// - `id`: Schema id found in the wire format header.
+  fn schema(&self, id: u32) -> Result<Arc<Schema>, RegistryError>;
+}
+
+#[derive(Debug, Default)]
+struct MemoryRegistryState {
+  schemas: Vec<Arc<Schema>>,
+  ids: HashMap<String, u32>,
+  subjects: HashMap<String, Vec<u32>>,
+}
+
+/// In-memory [`SchemaRegistry`] implementation.
+///
+/// Ids are assigned sequentially starting from 1, and schemas with the same
+/// [canonical form](Schema::canonical_form) share the same id regardless of
+/// the subject they are registered under, just like a Confluent registry.
+/// This is intended for tests and local development.
+#[derive(Debug, Default)]
+pub struct MemoryRegistry {
+  state: RwLock<MemoryRegistryState>,
+}
+
+impl MemoryRegistry {
+  /// Creates an empty registry.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the ids registered under `subject` in registration order.
+  pub fn versions(&self, subject: &str) -> Vec<u32> {
+    let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+    state.subjects.get(subject).cloned().unwrap_or_default()
+  }
+}
+
+impl SchemaRegistry for MemoryRegistry {
+  fn register(
+    &self,
+    subject: &str,
+    schema: &Schema,
+  ) -> Result<u32, RegistryError> {
+    let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+    let canonical = schema.canonical_form();
+    let id = match state.ids.get(&canonical) {
+      Some(id) => *id,
+      None => {
+        state.schemas.push(Arc::new(schema.clone()));
+        let id = state.schemas.len() as u32;
+        state.ids.insert(canonical, id);
+        id
+      }
+    };
+    let versions = state.subjects.entry(subject.to_string()).or_default();
+    if !versions.contains(&id) {
+      versions.push(id);
+    }
+    Ok(id)
+  }
+
+  fn schema(&self, id: u32) -> Result<Arc<Schema>, RegistryError> {
+    let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+    id.checked_sub(1)
+      .and_then(|idx| state.schemas.get(idx as usize))
+      .cloned()
+      .ok_or(RegistryError::NotFound(id))
+  }
+}
@@ -0,0 +1,132 @@
+use ::std::sync::Arc;
+
+use ::apache_avro::Schema;
+use ::bytes::Bytes;
+use ::serde::{Deserialize, Serialize};
+
+use crate::encoders::{Decoder as IDecoder, Encoder as IEncoder};
+use crate::tests::entity::TestEntity;
+
+use super::{
+  AvroError, Decoder, Encoder, MemoryRegistry, RegistryError, SchemaRegistry,
+};
+
+const ENTITY_V1: &str = r#"{
+  "type": "record",
+  "name": "TestEntity",
+  "fields": [
+    {"name": "id", "type": "int"},
+    {"name": "name", "type": "string"}
+  ]
+}"#;
+
+const ENTITY_V2: &str = r#"{
+  "type": "record",
+  "name": "TestEntity",
+  "fields": [
+    {"name": "id", "type": "int"},
+    {"name": "name", "type": "string"},
+    {"name": "email", "type": ["null", "string"], "default": null}
+  ]
+}"#;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct TestEntityV2 {
+  id: u32,
+  name: String,
+  email: Option<String>,
+}
+
+fn schema(raw: &str) -> Schema {
+  Schema::parse_str(raw).unwrap()
+}
+
+#[test]
+fn test_encode_decode_roundtrip() {
+  let registry = Arc::new(MemoryRegistry::new());
+  let encoder =
+    Encoder::new(registry.clone(), "entity-value", schema(ENTITY_V1)).unwrap();
+  let decoder = Decoder::<TestEntity>::new(registry);
+
+  let original = TestEntity::new(42, "roundtrip");
+  let encoded = encoder.encode(&original).unwrap();
+  let decoded = decoder.decode(encoded).unwrap();
+
+  assert_eq!(decoded, original);
+}
+
+#[test]
+fn test_wire_format_header() {
+  let registry = Arc::new(MemoryRegistry::new());
+  registry
+    .register("other-value", &schema(ENTITY_V2))
+    .unwrap();
+  let encoder =
+    Encoder::new(registry.clone(), "entity-value", schema(ENTITY_V1)).unwrap();
+
+  let encoded = encoder.encode(&TestEntity::new(1, "a")).unwrap();
+
+  assert_eq!(encoder.schema_id(), 2);
+  assert_eq!(&encoded[..5], &[0, 0, 0, 0, 2]);
+}
+
+#[test]
+fn test_register_is_idempotent() {
+  let registry = Arc::new(MemoryRegistry::new());
+  let first =
+    Encoder::<TestEntity>::new(registry.clone(), "a-value", schema(ENTITY_V1))
+      .unwrap();
+  let second =
+    Encoder::<TestEntity>::new(registry.clone(), "b-value", schema(ENTITY_V1))
+      .unwrap();
+
+  assert_eq!(first.schema_id(), second.schema_id());
+  assert_eq!(registry.versions("a-value"), vec![first.schema_id()]);
+  assert_eq!(registry.versions("b-value"), vec![first.schema_id()]);
+}
+
+#[test]
+fn test_reader_schema_evolution() {
+  let registry = Arc::new(MemoryRegistry::new());
+  let encoder =
+    Encoder::new(registry.clone(), "entity-value", schema(ENTITY_V1)).unwrap();
+  let decoder =
+    Decoder::<TestEntityV2>::new(registry).reader_schema(schema(ENTITY_V2));
+
+  let encoded = encoder.encode(&TestEntity::new(7, "old")).unwrap();
+  let decoded = decoder.decode(encoded).unwrap();
+
+  assert_eq!(
+    decoded,
+    TestEntityV2 {
+      id: 7,
+      name: "old".to_string(),
+      email: None,
+    }
+  );
+}
+
+#[test]
+fn test_unknown_schema_id() {
+  let registry = Arc::new(MemoryRegistry::new());
+  let decoder = Decoder::<TestEntity>::new(registry);
+
+  let result = decoder.decode(Bytes::from_static(&[0, 0, 0, 0, 9, 2]));
+
+  assert!(matches!(
+    result,
+    Err(AvroError::Registry(RegistryError::NotFound(9)))
+  ));
+}
+
+#[test]
+fn test_invalid_header() {
+  let registry = Arc::new(MemoryRegistry::new());
+  let decoder = Decoder::<TestEntity>::new(registry);
+
+  let short = decoder.decode(Bytes::from_static(&[0, 0, 1]));
+  let magic = decoder.decode(Bytes::from_static(&[1, 0, 0, 0, 1, 2]));
+
+  assert!(matches!(short, Err(AvroError::TruncatedHeader(3))));
+  assert!(matches!(magic, Err(AvroError::InvalidMagicByte(1))));
+}
@@ -0,0 +1,185 @@
+//! Bincode encoder and decoder implementations.
+//!
+//! This module provides `Encoder` and `Decoder` trait implementations for the
+//! [bincode](https://docs.rs/bincode) binary format. Bincode is a compact,
+//! non-self-describing format that is very fast to encode and decode, which
+//! makes it a good fit for Rust-to-Rust internal traffic where both sides
+//! share the same type definitions.
+//!
+//! Both the encoder and the decoder use the
+//! [`bincode::config::standard`] configuration (little endian,
+//! variable-length integers), so payloads are compatible with any other
+//! bincode 2 peer using the default configuration.
+//!
+//! # Example
+//!
+//! ```rust
+//! use object_transfer::encoders::{Encoder, Decoder};
+//! use object_transfer::encoders::bincode::{
+//!   Encoder as BincodeEncoder,
+//!   Decoder as BincodeDecoder,
+//! };
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Message {
+//!     id: u32,
+//!     content: String,
+//! }
+//!
+//! let encoder = BincodeEncoder::new();
+//! let decoder = BincodeDecoder::new();
+//!
+//! let msg = Message { id: 1, content: "Hello".to_string() };
+//! let encoded = encoder.encode(&msg)?;
+//! let decoded: Message = decoder.decode(encoded)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::marker::PhantomData;
+
+use ::bincode::{
+  config::standard,
+  error::{DecodeError, EncodeError},
+  serde::{decode_from_slice, encode_into_std_write, encode_to_vec},
+};
+use ::bytes::{Bytes, BytesMut};
+use ::serde::{de::DeserializeOwned, ser::Serialize};
+
+use super::buf_writer::BufWriter;
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// A bincode encoder for serializing data structures to bincode format.
+///
+/// `Encoder<T>` implements the [`Encoder`](super::traits::Encoder) trait to
+/// provide bincode serialization for any type `T` that implements
+/// [`serde::Serialize`].
+///
+/// # Type Parameters
+///
+/// * `T` - The data type to be encoded. Must implement [`Serialize`],
+///   [`Send`], and [`Sync`].
+#[derive(Debug)]
+pub struct Encoder<T: Serialize + Send + Sync> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: Serialize + Send + Sync> Encoder<T> {
+  /// Creates a new bincode encoder.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: Serialize + Send + Sync> Default for Encoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Serialize + Send + Sync> EncoderTrait for Encoder<T> {
+  type Item = T;
+  type Error = EncodeError;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    Ok(Bytes::from(encode_to_vec(item, standard())?))
+  }
+
+  fn encode_into(
+    &self,
+    item: &Self::Item,
+    buf: &mut BytesMut,
+  ) -> Result<(), Self::Error> {
+    encode_into_std_write(item, &mut BufWriter(buf), standard())?;
+    Ok(())
+  }
+}
+
+/// A bincode decoder for deserializing data structures from bincode format.
+///
+/// `Decoder<T>` implements the [`Decoder`](super::traits::Decoder) trait to
+/// provide bincode deserialization for any type `T` that implements
+/// [`serde::Deserialize`].
+///
+/// # Type Parameters
+///
+/// * `T` - The data type to be decoded. Must implement [`DeserializeOwned`],
+///   [`Send`], and [`Sync`].
+#[derive(Debug)]
+pub struct Decoder<T: DeserializeOwned + Send + Sync> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned + Send + Sync> Decoder<T> {
+  /// Creates a new bincode decoder.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: DeserializeOwned + Send + Sync> Default for Decoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: DeserializeOwned + Send + Sync> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = DecodeError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    let (item, _) = decode_from_slice(&data, standard())?;
+    Ok(item)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::tests::entity::TestEntity;
+
+  use super::*;
+
+  #[test]
+  fn test_encode_decode_roundtrip() {
+    let encoder = Encoder::new();
+    let decoder = Decoder::new();
+
+    let original = TestEntity::new(123, "roundtrip_test");
+
+    let encoded = encoder.encode(&original).expect("encoding failed");
+    let decoded: TestEntity =
+      decoder.decode(encoded).expect("decoding failed");
+
+    assert_eq!(decoded, original);
+  }
+
+  #[test]
+  fn test_bincode_decoder_truncated_payload() {
+    let encoder = Encoder::new();
+    let decoder = Decoder::<TestEntity>::new();
+
+    let encoded = encoder
+      .encode(&TestEntity::new(1, "truncated"))
+      .expect("encoding failed");
+    let result = decoder.decode(encoded.slice(..encoded.len() - 1));
+
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn test_encode_into_matches_encode() {
+    let encoder = Encoder::new();
+    let entity = TestEntity::new(1, "buffered");
+    let mut buf = BytesMut::new();
+
+    encoder
+      .encode_into(&entity, &mut buf)
+      .expect("encoding failed");
+
+    assert_eq!(buf.freeze(), encoder.encode(&entity).unwrap());
+  }
+}
@@ -0,0 +1,120 @@
+//! Zero-copy decoding of items borrowing from the payload.
+//!
+//! [`Decoder`](super::Decoder) produces owned items, so every string field
+//! of every message is allocated. For high-volume topics, the decoders of
+//! this module produce a [`Borrowed`] guard instead: the guard holds the
+//! payload together with an item whose fields borrow from it, e.g.
+//! `&'a str` or `Cow<'a, str>` fields marked with `#[serde(borrow)]`.
+//!
+//! Item types are declared with their borrowed lifetime and derive
+//! [`Yokeable`], and decoders are parameterized by the `'static` form of the
+//! type. Since the borrowed decoders also implement
+//! [`Decoder`](super::Decoder) with [`Borrowed`] items, they can be used
+//! with [`Sub`](crate::Sub) like any other decoder.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::borrow::Cow;
+//! use bytes::Bytes;
+//! use serde::Deserialize;
+//! use yoke::Yokeable;
+//! use object_transfer::encoders::Decoder;
+//! use object_transfer::encoders::borrowed;
+//!
+//! #[derive(Deserialize, Yokeable)]
+//! struct Event<'a> {
+//!     id: u32,
+//!     #[serde(borrow)]
+//!     name: Cow<'a, str>,
+//! }
+//!
+//! let decoder = borrowed::JSONDecoder::<Event<'static>>::new();
+//! let event = decoder.decode(Bytes::from_static(br#"{"id": 1, "name": "a"}"#))?;
+//!
+//! assert_eq!(event.get().name, "a");
+//! assert!(matches!(event.get().name, Cow::Borrowed(_)));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::error::Error as StdError;
+
+use ::bytes::Bytes;
+use ::yoke::{Yoke, Yokeable};
+
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+
+#[cfg(feature = "json")]
+pub use self::json::Decoder as JSONDecoder;
+#[cfg(feature = "msgpack")]
+pub use self::msgpack::Decoder as MessagePackDecoder;
+
+/// An item borrowing from the payload it was decoded from.
+///
+/// # Type Parameters
+///
+/// * `Y` - The `'static` form of the item type, e.g. `Event<'static>`.
+pub struct Borrowed<Y: for<'a> Yokeable<'a>> {
+  inner: Yoke<Y, Box<Bytes>>,
+}
+
+impl<Y: for<'a> Yokeable<'a>> Borrowed<Y> {
+  /// Decodes `data` with `decode` and keeps the payload alive alongside the
+  /// decoded item.
+  ///
+  /// # Parameters
+  /// - `data`: The payload.
+  /// - `decode`: Decodes the item borrowing from the payload.
+  pub fn try_new<E, F>(data: Bytes, decode: F) -> Result<Self, E>
+  where
+    F: for<'de> FnOnce(&'de [u8]) -> Result<<Y as Yokeable<'de>>::Output, E>,
+  {
+    let inner =
+      Yoke::try_attach_to_cart(Box::new(data), |data: &Bytes| decode(data))?;
+    Ok(Self { inner })
+  }
+
+  /// Returns the decoded item.
+  pub fn get(&self) -> &<Y as Yokeable<'_>>::Output {
+    self.inner.get()
+  }
+
+  /// Returns the payload the item borrows from.
+  pub fn bytes(&self) -> &Bytes {
+    self.inner.backing_cart()
+  }
+
+  /// Drops the item and returns the payload.
+  pub fn into_bytes(self) -> Bytes {
+    *self.inner.into_backing_cart()
+  }
+}
+
+/// A trait for decoding items borrowing from the payload.
+///
+/// # Associated Types
+///
+/// * `Item` - The `'static` form of the item type. Its borrowed form is
+///   `<Self::Item as Yokeable<'de>>::Output`.
+/// * `Error` - The error type returned when decoding fails.
+pub trait BorrowDecoder {
+  type Item: for<'a> Yokeable<'a>;
+  type Error: StdError + Send + Sync;
+
+  /// Decodes an item borrowing from `data`.
+  fn decode_borrowed<'de>(
+    &self,
+    data: &'de [u8],
+  ) -> Result<<Self::Item as Yokeable<'de>>::Output, Self::Error>;
+
+  /// Decodes an item borrowing from `data`, and wraps both into a guard.
+  fn decode_guarded(
+    &self,
+    data: Bytes,
+  ) -> Result<Borrowed<Self::Item>, Self::Error> {
+    Borrowed::try_new(data, |data| self.decode_borrowed(data))
+  }
+}
@@ -0,0 +1,123 @@
+use ::std::marker::PhantomData;
+
+use ::bytes::Bytes;
+use ::serde::Deserialize;
+use ::serde_json::{Error as JSErr, from_slice};
+use ::yoke::Yokeable;
+
+use super::super::traits::Decoder as DecoderTrait;
+use super::{BorrowDecoder, Borrowed};
+
+/// Decodes JSON byte sequences into items borrowing from the payload.
+///
+/// Strings containing escape sequences can't be borrowed; use `Cow<'a, str>`
+/// fields to fall back to an allocation for them.
+///
+/// # Type Parameters
+///
+/// * `Y` - The `'static` form of the item type.
+#[derive(Debug)]
+pub struct Decoder<Y> {
+  _marker: PhantomData<Y>,
+}
+
+impl<Y> Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a>,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  /// Creates a new instance of `Decoder`.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<Y> Default for Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a>,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Y> BorrowDecoder for Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a>,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  type Item = Y;
+  type Error = JSErr;
+
+  fn decode_borrowed<'de>(
+    &self,
+    data: &'de [u8],
+  ) -> Result<<Y as Yokeable<'de>>::Output, Self::Error> {
+    from_slice(data)
+  }
+}
+
+impl<Y> DecoderTrait for Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a> + Send + Sync,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  type Item = Borrowed<Y>;
+  type Error = JSErr;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    self.decode_guarded(data)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::std::borrow::Cow;
+
+  use super::*;
+
+  #[derive(Debug, Deserialize, Yokeable)]
+  struct Event<'a> {
+    id: u32,
+    name: &'a str,
+    #[serde(borrow)]
+    note: Cow<'a, str>,
+  }
+
+  #[test]
+  fn test_decode_borrows_from_payload() {
+    let decoder = Decoder::<Event<'static>>::new();
+    let data = Bytes::from_static(br#"{"id": 1, "name": "a", "note": "b"}"#);
+
+    let event = decoder.decode(data.clone()).unwrap();
+
+    let range = data.as_ptr_range();
+    assert_eq!(event.get().id, 1);
+    assert!(range.contains(&event.get().name.as_ptr()));
+    assert!(matches!(event.get().note, Cow::Borrowed("b")));
+    assert_eq!(event.into_bytes(), data);
+  }
+
+  #[test]
+  fn test_escaped_string_is_owned() {
+    let decoder = Decoder::<Event<'static>>::new();
+
+    let event = decoder
+      .decode(Bytes::from_static(
+        br#"{"id": 1, "name": "a", "note": "\"b\""}"#,
+      ))
+      .unwrap();
+
+    assert!(matches!(&event.get().note, Cow::Owned(note) if note == "\"b\""));
+  }
+
+  #[test]
+  fn test_decode_error() {
+    let decoder = Decoder::<Event<'static>>::new();
+
+    assert!(decoder.decode(Bytes::from_static(b"{")).is_err());
+  }
+}
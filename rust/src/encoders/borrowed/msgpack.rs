@@ -0,0 +1,108 @@
+use ::std::marker::PhantomData;
+
+use ::bytes::Bytes;
+use ::rmp_serde::decode::{Error as DecodeError, from_slice};
+use ::serde::Deserialize;
+use ::yoke::Yokeable;
+
+use super::super::traits::Decoder as DecoderTrait;
+use super::{BorrowDecoder, Borrowed};
+
+/// Decodes MessagePack byte sequences into items borrowing from the payload.
+///
+/// # Type Parameters
+///
+/// * `Y` - The `'static` form of the item type.
+#[derive(Debug)]
+pub struct Decoder<Y> {
+  _marker: PhantomData<Y>,
+}
+
+impl<Y> Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a>,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  /// Creates a new MessagePack decoder.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<Y> Default for Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a>,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<Y> BorrowDecoder for Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a>,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  type Item = Y;
+  type Error = DecodeError;
+
+  fn decode_borrowed<'de>(
+    &self,
+    data: &'de [u8],
+  ) -> Result<<Y as Yokeable<'de>>::Output, Self::Error> {
+    from_slice(data)
+  }
+}
+
+impl<Y> DecoderTrait for Decoder<Y>
+where
+  Y: for<'a> Yokeable<'a> + Send + Sync,
+  for<'de> <Y as Yokeable<'de>>::Output: Deserialize<'de>,
+{
+  type Item = Borrowed<Y>;
+  type Error = DecodeError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    self.decode_guarded(data)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::serde::Serialize;
+
+  use super::*;
+
+  #[derive(Serialize)]
+  struct Owned {
+    id: u32,
+    name: String,
+  }
+
+  #[derive(Debug, Deserialize, Yokeable)]
+  struct Event<'a> {
+    id: u32,
+    name: &'a str,
+  }
+
+  #[test]
+  fn test_decode_borrows_from_payload() {
+    let data = Bytes::from(
+      ::rmp_serde::to_vec(&Owned {
+        id: 1,
+        name: "a".to_string(),
+      })
+      .unwrap(),
+    );
+    let decoder = Decoder::<Event<'static>>::new();
+
+    let event = decoder.decode(data.clone()).unwrap();
+
+    assert_eq!(event.get().id, 1);
+    assert_eq!(event.get().name, "a");
+    assert!(data.as_ptr_range().contains(&event.get().name.as_ptr()));
+  }
+}
@@ -0,0 +1,28 @@
+use ::std::io::{Result, Write};
+
+use ::bytes::BytesMut;
+
+/// [`Write`] adapter appending to a [`BytesMut`].
+///
+/// Serializers emit many tiny writes; unlike
+/// [`BufMut::writer`](::bytes::BufMut::writer), this adapter overrides
+/// [`Write::write_all`] so that each of them is a single copy.
+pub(crate) struct BufWriter<'a>(pub(crate) &'a mut BytesMut);
+
+impl Write for BufWriter<'_> {
+  #[inline]
+  fn write(&mut self, buf: &[u8]) -> Result<usize> {
+    self.0.extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  #[inline]
+  fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+    self.0.extend_from_slice(buf);
+    Ok(())
+  }
+
+  fn flush(&mut self) -> Result<()> {
+    Ok(())
+  }
+}
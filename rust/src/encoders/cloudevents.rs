@@ -0,0 +1,585 @@
+//! CloudEvents 1.0 encoder and decoder in structured JSON content mode.
+//!
+//! The [`Encoder`] wraps any inner encoder producing the event data and
+//! writes a [CloudEvents](https://github.com/cloudevents/spec) structured
+//! JSON document with the `specversion`, `id`, `source`, `type`, `time` and
+//! `datacontenttype` attributes, so that object_transfer producers
+//! interoperate with CloudEvents consumers. The [`Decoder`] reads such
+//! documents back into an [`Event`] holding the attributes and the data
+//! decoded by an inner decoder.
+//!
+//! When the data content type is JSON (`application/json`, `text/json` or
+//! any `+json` suffix), the data is embedded as-is in the `data` attribute;
+//! otherwise it is base64 encoded in the `data_base64` attribute. Events
+//! without data, which the specification allows, are decoded from a JSON
+//! `null` for JSON content types and from an empty payload otherwise, so
+//! decoding them into e.g. an `Option` or a unit type succeeds.
+//!
+//! Binary content mode maps the attributes to message headers, and will be
+//! supported once the broker traits carry headers.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use serde::{Serialize, Deserialize};
+//! use object_transfer::encoders::{Encoder, Decoder};
+//! use object_transfer::encoders::{JSONEncoder, JSONDecoder};
+//! use object_transfer::encoders::cloudevents;
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct UserCreated {
+//!     id: u32,
+//! }
+//!
+//! let encoder = cloudevents::Encoder::new(
+//!   "/users",
+//!   "com.example.user.created",
+//!   Arc::new(JSONEncoder::new()),
+//! );
+//! let decoder = cloudevents::Decoder::new(
+//!   Arc::new(JSONDecoder::<UserCreated>::new()),
+//! );
+//!
+//! let event = decoder.decode(encoder.encode(&UserCreated { id: 1 })?)?;
+//! assert_eq!(event.source, "/users");
+//! assert_eq!(event.ty, "com.example.user.created");
+//! assert_eq!(event.data, UserCreated { id: 1 });
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::error::Error as StdError;
+use ::std::sync::Arc;
+
+use ::base64::Engine;
+use ::base64::engine::general_purpose::STANDARD as BASE64;
+use ::bytes::Bytes;
+use ::serde_json::{Map, Value};
+use ::thiserror::Error;
+use ::time::OffsetDateTime;
+use ::time::format_description::well_known::Rfc3339;
+use ::uuid::Uuid;
+
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// The CloudEvents specification version written and accepted.
+pub const SPEC_VERSION: &str = "1.0";
+
+/// Longest attribute name recommended by the specification.
+const MAX_ATTRIBUTE_NAME_LEN: usize = 20;
+
+/// Attributes defined by the specification, which can't be used as
+/// extension names.
+const RESERVED: &[&str] = &[
+  "specversion",
+  "id",
+  "source",
+  "type",
+  "subject",
+  "time",
+  "datacontenttype",
+  "dataschema",
+  "data",
+  "data_base64",
+];
+
+/// Error type for CloudEvents encoding and decoding.
+#[derive(Error, Debug)]
+pub enum CloudEventsError {
+  /// The document isn't valid JSON, or JSON data isn't valid JSON.
+  #[error("JSON Error: {0}")]
+  Json(#[from] ::serde_json::Error),
+  /// The `data_base64` attribute isn't valid base64.
+  #[error("Base64 Error: {0}")]
+  Base64(#[from] ::base64::DecodeError),
+  /// The `time` attribute isn't a valid RFC 3339 timestamp.
+  #[error("Invalid time attribute: {0}")]
+  InvalidTime(String),
+  /// A required attribute is missing or isn't a string.
+  #[error("Missing or invalid attribute: {0}")]
+  MissingAttribute(&'static str),
+  /// An extension attribute uses a name defined by the specification.
+  #[error("Reserved attribute name: {0}")]
+  ReservedAttribute(String),
+  /// An extension attribute name isn't made of 1 to 20 lowercase ASCII
+  /// letters or digits.
+  #[error("Invalid attribute name: {0}")]
+  InvalidAttributeName(String),
+  /// The event uses an unsupported specification version.
+  #[error("Unsupported spec version: {0}")]
+  UnsupportedSpecVersion(String),
+  /// The inner encoder or decoder failed.
+  #[error("Data Error: {0}")]
+  Data(Box<dyn StdError + Send + Sync>),
+}
+
+/// A decoded CloudEvent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event<T> {
+  /// Identifies the event.
+  pub id: String,
+  /// Identifies the context in which the event happened.
+  pub source: String,
+  /// Type of the event (the `type` attribute).
+  pub ty: String,
+  /// Subject of the event in the context of the source.
+  pub subject: Option<String>,
+  /// Timestamp of when the occurrence happened.
+  pub time: Option<OffsetDateTime>,
+  /// Content type of the data.
+  pub datacontenttype: Option<String>,
+  /// Schema that the data adheres to.
+  pub dataschema: Option<String>,
+  /// Extension attributes.
+  pub extensions: Map<String, Value>,
+  /// The event payload.
+  pub data: T,
+}
+
+/// Returns `true` if the media type denotes JSON data.
+fn is_json(content_type: &str) -> bool {
+  let media_type = content_type.split(';').next().unwrap_or("").trim();
+  media_type.eq_ignore_ascii_case("application/json")
+    || media_type.eq_ignore_ascii_case("text/json")
+    || media_type.to_ascii_lowercase().ends_with("+json")
+}
+
+/// Encoder wrapping the payloads of an inner encoder in CloudEvents.
+///
+/// Each encoded event gets a random UUID v4 `id` and the current time as
+/// `time`.
+pub struct Encoder<T, E: StdError + Send + Sync> {
+  source: String,
+  ty: String,
+  subject: Option<String>,
+  datacontenttype: String,
+  dataschema: Option<String>,
+  extensions: Map<String, Value>,
+  inner: Arc<dyn EncoderTrait<Item = T, Error = E> + Send + Sync>,
+}
+
+impl<T, E: StdError + Send + Sync> Encoder<T, E> {
+  /// Creates a new CloudEvents encoder with `application/json` data.
+  ///
+  /// # Parameters
+  /// - `source`: The `source` attribute of the events.
+  /// - `ty`: The `type` attribute of the events.
+  /// - `inner`: Encoder producing the event data.
+  pub fn new(
+    source: impl Into<String>,
+    ty: impl Into<String>,
+    inner: Arc<dyn EncoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> Self {
+    Self {
+      source: source.into(),
+      ty: ty.into(),
+      subject: None,
+      datacontenttype: "application/json".to_string(),
+      dataschema: None,
+      extensions: Map::new(),
+      inner,
+    }
+  }
+
+  /// Sets the `subject` attribute of the events.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn subject(mut self, subject: impl Into<String>) -> Self {
+    self.subject = Some(subject.into());
+    self
+  }
+
+  /// Sets the content type of the data produced by the inner encoder.
+  ///
+  /// Non-JSON data is written to the `data_base64` attribute.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn data_content_type(mut self, content_type: impl Into<String>) -> Self {
+    self.datacontenttype = content_type.into();
+    self
+  }
+
+  /// Sets the `dataschema` attribute of the events.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn data_schema(mut self, schema: impl Into<String>) -> Self {
+    self.dataschema = Some(schema.into());
+    self
+  }
+
+  /// Adds an extension attribute to the events.
+  ///
+  /// # Parameters
+  /// - `name`: Name of the extension attribute.
+  /// - `value`: Value of the extension attribute.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  ///
+  /// # Errors
+  /// Returns [`CloudEventsError::ReservedAttribute`] if `name` is an
+  /// attribute defined by the specification, and
+  /// [`CloudEventsError::InvalidAttributeName`] if `name` isn't made of
+  /// lowercase ASCII letters and digits, or is longer than the 20 characters
+  /// the specification recommends.
+  pub fn extension(
+    mut self,
+    name: impl Into<String>,
+    value: impl Into<Value>,
+  ) -> Result<Self, CloudEventsError> {
+    let name = name.into();
+    if RESERVED.contains(&name.as_str()) {
+      return Err(CloudEventsError::ReservedAttribute(name));
+    }
+    let valid = name
+      .bytes()
+      .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    if !valid || name.is_empty() || name.len() > MAX_ATTRIBUTE_NAME_LEN {
+      return Err(CloudEventsError::InvalidAttributeName(name));
+    }
+    self.extensions.insert(name, value.into());
+    Ok(self)
+  }
+}
+
+impl<T, E> EncoderTrait for Encoder<T, E>
+where
+  T: Send + Sync,
+  E: StdError + Send + Sync + 'static,
+{
+  type Item = T;
+  type Error = CloudEventsError;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    let payload = self
+      .inner
+      .encode(item)
+      .map_err(|e| CloudEventsError::Data(Box::new(e)))?;
+    let time = OffsetDateTime::now_utc()
+      .format(&Rfc3339)
+      .map_err(|e| CloudEventsError::InvalidTime(e.to_string()))?;
+    let mut doc = Map::new();
+    doc.insert("specversion".into(), SPEC_VERSION.into());
+    doc.insert("id".into(), Uuid::new_v4().to_string().into());
+    doc.insert("source".into(), self.source.clone().into());
+    doc.insert("type".into(), self.ty.clone().into());
+    if let Some(subject) = &self.subject {
+      doc.insert("subject".into(), subject.clone().into());
+    }
+    doc.insert("time".into(), time.into());
+    doc.insert(
+      "datacontenttype".into(),
+      self.datacontenttype.clone().into(),
+    );
+    if let Some(schema) = &self.dataschema {
+      doc.insert("dataschema".into(), schema.clone().into());
+    }
+    doc.extend(self.extensions.clone());
+    if is_json(&self.datacontenttype) {
+      doc.insert("data".into(), ::serde_json::from_slice(&payload)?);
+    } else {
+      doc.insert("data_base64".into(), BASE64.encode(&payload).into());
+    }
+    Ok(::serde_json::to_vec(&doc)?.into())
+  }
+}
+
+/// Decoder reading CloudEvents structured JSON documents.
+///
+/// The data is decoded with the inner decoder, from the JSON `data`
+/// attribute, the string `data` attribute of non-JSON content types, or the
+/// `data_base64` attribute. Events without data are decoded from a JSON
+/// `null` for JSON content types, and from an empty payload otherwise.
+pub struct Decoder<T, E: StdError + Send + Sync> {
+  inner: Arc<dyn DecoderTrait<Item = T, Error = E> + Send + Sync>,
+}
+
+impl<T, E> Decoder<T, E>
+where
+  T: Send + Sync,
+  E: StdError + Send + Sync + 'static,
+{
+  /// Creates a new CloudEvents decoder.
+  ///
+  /// # Parameters
+  /// - `inner`: Decoder for the event data.
+  pub fn new(
+    inner: Arc<dyn DecoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> Self {
+    Self { inner }
+  }
+
+  fn data(
+    &self,
+    doc: &mut Map<String, Value>,
+    content_type: Option<&str>,
+  ) -> Result<T, CloudEventsError> {
+    let payload: Bytes = match (doc.remove("data"), doc.remove("data_base64"))
+    {
+      (_, Some(Value::String(encoded))) => BASE64.decode(encoded)?.into(),
+      (_, Some(_)) => {
+        return Err(CloudEventsError::MissingAttribute("data_base64"));
+      }
+      (Some(Value::String(text)), None)
+        if !content_type.is_none_or(is_json) =>
+      {
+        text.into()
+      }
+      (Some(value), None) => ::serde_json::to_vec(&value)?.into(),
+      (None, None) if content_type.is_none_or(is_json) => {
+        Bytes::from_static(b"null")
+      }
+      (None, None) => Bytes::new(),
+    };
+    self
+      .inner
+      .decode(payload)
+      .map_err(|e| CloudEventsError::Data(Box::new(e)))
+  }
+}
+
+/// Removes a string attribute from the document.
+fn take_string(
+  doc: &mut Map<String, Value>,
+  name: &'static str,
+) -> Result<Option<String>, CloudEventsError> {
+  match doc.remove(name) {
+    None => Ok(None),
+    Some(Value::String(value)) => Ok(Some(value)),
+    Some(_) => Err(CloudEventsError::MissingAttribute(name)),
+  }
+}
+
+/// Removes a required string attribute from the document.
+fn require_string(
+  doc: &mut Map<String, Value>,
+  name: &'static str,
+) -> Result<String, CloudEventsError> {
+  take_string(doc, name)?.ok_or(CloudEventsError::MissingAttribute(name))
+}
+
+impl<T, E> DecoderTrait for Decoder<T, E>
+where
+  T: Send + Sync,
+  E: StdError + Send + Sync + 'static,
+{
+  type Item = Event<T>;
+  type Error = CloudEventsError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    let mut doc: Map<String, Value> = ::serde_json::from_slice(&data)?;
+    let spec_version = require_string(&mut doc, "specversion")?;
+    if spec_version != SPEC_VERSION {
+      return Err(CloudEventsError::UnsupportedSpecVersion(spec_version));
+    }
+    let id = require_string(&mut doc, "id")?;
+    let source = require_string(&mut doc, "source")?;
+    let ty = require_string(&mut doc, "type")?;
+    let subject = take_string(&mut doc, "subject")?;
+    let time = take_string(&mut doc, "time")?
+      .map(|time| {
+        OffsetDateTime::parse(&time, &Rfc3339)
+          .map_err(|_| CloudEventsError::InvalidTime(time))
+      })
+      .transpose()?;
+    let datacontenttype = take_string(&mut doc, "datacontenttype")?;
+    let dataschema = take_string(&mut doc, "dataschema")?;
+    let data = self.data(&mut doc, datacontenttype.as_deref())?;
+    Ok(Event {
+      id,
+      source,
+      ty,
+      subject,
+      time,
+      datacontenttype,
+      dataschema,
+      extensions: doc,
+      data,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::encoders::{JSONDecoder, JSONEncoder};
+  use crate::tests::entity::TestEntity;
+
+  use super::*;
+
+  fn encoder() -> Encoder<TestEntity, ::serde_json::Error> {
+    Encoder::new("/tests", "com.example.test", Arc::new(JSONEncoder::new()))
+  }
+
+  fn decoder() -> Decoder<TestEntity, ::serde_json::Error> {
+    Decoder::new(Arc::new(JSONDecoder::new()))
+  }
+
+  #[test]
+  fn test_structured_json() {
+    let encoder = encoder()
+      .subject("entity-1")
+      .data_schema("https://example.com/entity.json")
+      .extension("traceparent", "00-abc-def-01")
+      .unwrap();
+
+    let encoded = encoder.encode(&TestEntity::new(1, "test")).unwrap();
+    let doc: Value = ::serde_json::from_slice(&encoded).unwrap();
+
+    assert_eq!(doc["specversion"], "1.0");
+    assert_eq!(doc["source"], "/tests");
+    assert_eq!(doc["type"], "com.example.test");
+    assert_eq!(doc["subject"], "entity-1");
+    assert_eq!(doc["datacontenttype"], "application/json");
+    assert_eq!(doc["dataschema"], "https://example.com/entity.json");
+    assert_eq!(doc["traceparent"], "00-abc-def-01");
+    assert_eq!(doc["data"]["id"], 1);
+    assert!(Uuid::parse_str(doc["id"].as_str().unwrap()).is_ok());
+    assert!(
+      OffsetDateTime::parse(doc["time"].as_str().unwrap(), &Rfc3339).is_ok()
+    );
+  }
+
+  #[test]
+  fn test_roundtrip() {
+    let entity = TestEntity::new(2, "roundtrip");
+    let encoder = encoder().extension("tenant", "acme").unwrap();
+
+    let event = decoder().decode(encoder.encode(&entity).unwrap()).unwrap();
+
+    assert_eq!(event.data, entity);
+    assert_eq!(event.source, "/tests");
+    assert_eq!(event.ty, "com.example.test");
+    assert!(event.time.is_some());
+    assert_eq!(event.extensions["tenant"], "acme");
+  }
+
+  #[test]
+  fn test_ids_are_unique() {
+    let encoder = encoder();
+    let entity = TestEntity::new(3, "unique");
+
+    let first = decoder().decode(encoder.encode(&entity).unwrap()).unwrap();
+    let second = decoder().decode(encoder.encode(&entity).unwrap()).unwrap();
+
+    assert_ne!(first.id, second.id);
+  }
+
+  #[test]
+  fn test_binary_data_roundtrip() {
+    let entity = TestEntity::new(4, "binary");
+    let encoder = encoder().data_content_type("application/octet-stream");
+
+    let encoded = encoder.encode(&entity).unwrap();
+    let doc: Value = ::serde_json::from_slice(&encoded).unwrap();
+    let event = decoder().decode(encoded).unwrap();
+
+    assert!(doc.get("data").is_none());
+    assert!(doc["data_base64"].is_string());
+    assert_eq!(event.data, entity);
+  }
+
+  #[test]
+  fn test_decode_foreign_event() {
+    let doc = br#"{
+      "specversion": "1.0",
+      "id": "A234-1234-1234",
+      "source": "https://github.com/cloudevents/spec/pull",
+      "type": "com.github.pull_request.opened",
+      "time": "2018-04-05T17:31:00Z",
+      "comexampleextension1": "value",
+      "data": {"id": 5, "name": "foreign"}
+    }"#;
+
+    let event = decoder().decode(Bytes::from_static(doc)).unwrap();
+
+    assert_eq!(event.id, "A234-1234-1234");
+    assert_eq!(event.datacontenttype, None);
+    assert_eq!(event.time.unwrap().unix_timestamp(), 1522949460);
+    assert_eq!(event.extensions["comexampleextension1"], "value");
+    assert_eq!(event.data, TestEntity::new(5, "foreign"));
+  }
+
+  #[test]
+  fn test_missing_attribute() {
+    let doc = br#"{"specversion": "1.0", "id": "1", "type": "t", "data": {}}"#;
+
+    let result = decoder().decode(Bytes::from_static(doc));
+
+    assert!(matches!(
+      result,
+      Err(CloudEventsError::MissingAttribute("source"))
+    ));
+  }
+
+  #[test]
+  fn test_unsupported_spec_version() {
+    let doc = br#"{"specversion": "0.3", "id": "1", "source": "s",
+      "type": "t", "data": {}}"#;
+
+    let result = decoder().decode(Bytes::from_static(doc));
+
+    assert!(matches!(
+      result,
+      Err(CloudEventsError::UnsupportedSpecVersion(v)) if v == "0.3"
+    ));
+  }
+
+  #[test]
+  fn test_reserved_extension() {
+    let result = encoder().extension("source", "/other");
+
+    assert!(matches!(
+      result,
+      Err(CloudEventsError::ReservedAttribute(name)) if name == "source"
+    ));
+  }
+
+  #[test]
+  fn test_invalid_extension_name() {
+    for name in ["traceParent", "my-ext", "", "abcdefghijklmnopqrstu"] {
+      let result = encoder().extension(name, "value");
+
+      assert!(
+        matches!(
+          &result,
+          Err(CloudEventsError::InvalidAttributeName(invalid)) if invalid == name
+        ),
+        "{name:?} should be rejected",
+      );
+    }
+  }
+
+  #[test]
+  fn test_valid_extension_name() {
+    let result = encoder().extension("abcdefghijklmnopq123", "value");
+
+    assert!(result.is_ok());
+  }
+
+  #[test]
+  fn test_event_without_data() {
+    let doc = br#"{"specversion": "1.0", "id": "1", "source": "s",
+      "type": "t"}"#;
+    let decoder =
+      Decoder::new(Arc::new(JSONDecoder::<Option<TestEntity>>::new()));
+
+    let event = decoder.decode(Bytes::from_static(doc)).unwrap();
+
+    assert_eq!(event.data, None);
+  }
+
+  #[test]
+  fn test_binary_event_without_data() {
+    let doc = br#"{"specversion": "1.0", "id": "1", "source": "s",
+      "type": "t", "datacontenttype": "application/octet-stream"}"#;
+    let decoder =
+      Decoder::new(Arc::new(crate::encoders::raw::Decoder::<Bytes>::new()));
+
+    let event = decoder.decode(Bytes::from_static(doc)).unwrap();
+
+    assert!(event.data.is_empty());
+  }
+}
@@ -1,9 +1,10 @@
 use ::std::marker::PhantomData;
 
-use ::bytes::Bytes;
+use ::bytes::{Bytes, BytesMut};
 use ::serde::{de::DeserializeOwned, ser::Serialize};
-use ::serde_json::{Error as JSErr, from_slice, to_vec};
+use ::serde_json::{Error as JSErr, from_slice, to_vec, to_writer};
 
+use super::buf_writer::BufWriter;
 use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
 
 /// Encodes items into JSON byte sequences.
@@ -64,6 +65,14 @@ impl<T: Serialize + Send + Sync> EncoderTrait for Encoder<T> {
     let payload = to_vec(item)?;
     Ok(Bytes::from(payload))
   }
+
+  fn encode_into(
+    &self,
+    item: &Self::Item,
+    buf: &mut BytesMut,
+  ) -> Result<(), Self::Error> {
+    to_writer(BufWriter(buf), item)
+  }
 }
 
 /// Decodes JSON byte sequences into items.
@@ -232,4 +241,17 @@ mod test {
 
     assert_eq!(decoded, original);
   }
+
+  #[test]
+  fn test_encode_into_appends_to_buffer() {
+    let encoder = Encoder::new();
+    let entity = TestEntity::new(1, "buffered");
+    let mut buf = BytesMut::from(&b"prefix"[..]);
+
+    encoder.encode_into(&entity, &mut buf).unwrap();
+
+    let expected = encoder.encode(&entity).unwrap();
+    assert_eq!(&buf[..6], b"prefix");
+    assert_eq!(&buf[6..], &expected[..]);
+  }
 }
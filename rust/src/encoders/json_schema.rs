@@ -0,0 +1,218 @@
+//! JSON decoder validating payloads against a JSON Schema.
+//!
+//! This module provides a [`Decoder`] for topics fed by untrusted producers:
+//! every payload is validated against a JSON Schema before it is
+//! deserialized, and payloads violating the schema are rejected with the
+//! instance path of each violation. The schema is either provided as a JSON
+//! value or generated from the target type with [`schemars`].
+//!
+//! Encoding is unaffected; use the regular
+//! [`JSONEncoder`](crate::encoders::JSONEncoder) on the producer side.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bytes::Bytes;
+//! use schemars::JsonSchema;
+//! use serde::Deserialize;
+//! use object_transfer::encoders::Decoder;
+//! use object_transfer::encoders::json_schema::{self, JsonSchemaError};
+//!
+//! #[derive(Deserialize, JsonSchema, Debug)]
+//! struct Event {
+//!     id: u32,
+//!     name: String,
+//! }
+//!
+//! let decoder = json_schema::Decoder::<Event>::from_type()?;
+//!
+//! let err = decoder
+//!   .decode(Bytes::from_static(br#"{"id": -1, "name": "event"}"#))
+//!   .unwrap_err();
+//! let JsonSchemaError::Validation(err) = err else { unreachable!() };
+//! assert_eq!(err.violations()[0].field, "/id");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::marker::PhantomData;
+
+use ::bytes::Bytes;
+use ::jsonschema::Validator;
+use ::schemars::JsonSchema;
+use ::serde::de::DeserializeOwned;
+use ::serde_json::Value;
+use ::thiserror::Error;
+
+use super::traits::Decoder as DecoderTrait;
+use crate::errors::{ValidationError, Violation};
+
+/// Error type for the JSON Schema validating decoder.
+#[derive(Error, Debug)]
+pub enum JsonSchemaError {
+  /// The payload isn't valid JSON, or doesn't deserialize into the target
+  /// type.
+  #[error("JSON Error: {0}")]
+  Json(#[from] ::serde_json::Error),
+  /// The schema itself is invalid.
+  #[error("Invalid schema: {0}")]
+  InvalidSchema(String),
+  /// The payload violates the schema.
+  #[error("Schema violation: {0}")]
+  Validation(#[from] ValidationError),
+}
+
+/// A JSON decoder validating payloads against a JSON Schema.
+///
+/// Each violation is reported as a [`Violation`] whose `field` is the JSON
+/// pointer of the offending value (e.g. `/items/0/name`), whose `code` is the
+/// failed schema keyword (e.g. `minimum`), and whose `message` describes the
+/// failure.
+///
+/// # Type Parameters
+///
+/// * `T` - The data type to be decoded. Must implement [`DeserializeOwned`],
+///   [`Send`], and [`Sync`].
+pub struct Decoder<T: DeserializeOwned + Send + Sync> {
+  validator: Validator,
+  _marker: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned + Send + Sync> Decoder<T> {
+  /// Creates a new decoder validating payloads against `schema`.
+  ///
+  /// # Parameters
+  /// - `schema`: The JSON Schema document.
+  pub fn new(schema: &Value) -> Result<Self, JsonSchemaError> {
+    let validator = ::jsonschema::validator_for(schema)
+      .map_err(|e| JsonSchemaError::InvalidSchema(e.to_string()))?;
+    Ok(Self {
+      validator,
+      _marker: PhantomData,
+    })
+  }
+
+  /// Creates a new decoder validating payloads against the schema generated
+  /// from `T`.
+  pub fn from_type() -> Result<Self, JsonSchemaError>
+  where
+    T: JsonSchema,
+  {
+    Self::new(::schemars::schema_for!(T).as_value())
+  }
+}
+
+impl<T: DeserializeOwned + Send + Sync> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = JsonSchemaError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    let value: Value = ::serde_json::from_slice(&data)?;
+    let violations: Vec<Violation> = self
+      .validator
+      .iter_errors(&value)
+      .map(|err| {
+        Violation::new(err.instance_path().as_str(), err.kind().keyword())
+          .message(err.to_string())
+      })
+      .collect();
+    if !violations.is_empty() {
+      return Err(ValidationError::new(violations).into());
+    }
+    Ok(::serde_json::from_value(value)?)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use ::serde::Deserialize;
+  use ::serde_json::json;
+
+  use super::*;
+
+  #[derive(Debug, PartialEq, Deserialize, JsonSchema)]
+  struct Item {
+    name: String,
+  }
+
+  #[derive(Debug, PartialEq, Deserialize, JsonSchema)]
+  struct Order {
+    id: u32,
+    items: Vec<Item>,
+  }
+
+  #[test]
+  fn test_decode_valid() {
+    let decoder = Decoder::<Order>::from_type().unwrap();
+
+    let order = decoder
+      .decode(Bytes::from_static(
+        br#"{"id": 1, "items": [{"name": "a"}]}"#,
+      ))
+      .unwrap();
+
+    assert_eq!(
+      order,
+      Order {
+        id: 1,
+        items: vec![Item {
+          name: "a".to_string()
+        }],
+      }
+    );
+  }
+
+  #[test]
+  fn test_violation_paths() {
+    let decoder = Decoder::<Order>::from_type().unwrap();
+
+    let result = decoder
+      .decode(Bytes::from_static(br#"{"id": -1, "items": [{"name": 1}]}"#));
+
+    let Err(JsonSchemaError::Validation(err)) = result else {
+      panic!("unexpected result: {result:?}");
+    };
+    let mut paths: Vec<_> = err
+      .violations()
+      .iter()
+      .map(|v| (v.field.as_str(), v.code.as_str()))
+      .collect();
+    paths.sort();
+    assert_eq!(paths, vec![("/id", "minimum"), ("/items/0/name", "type")]);
+  }
+
+  #[test]
+  fn test_provided_schema() {
+    let schema = json!({
+      "type": "object",
+      "properties": {"name": {"type": "string", "minLength": 3}},
+      "required": ["name"],
+    });
+    let decoder = Decoder::<Item>::new(&schema).unwrap();
+
+    let valid = decoder.decode(Bytes::from_static(br#"{"name": "abc"}"#));
+    let invalid = decoder.decode(Bytes::from_static(br#"{"name": "a"}"#));
+
+    assert!(valid.is_ok());
+    assert!(matches!(
+      invalid,
+      Err(JsonSchemaError::Validation(err))
+        if err.violations()[0].code == "minLength"
+    ));
+  }
+
+  #[test]
+  fn test_invalid_schema() {
+    let result = Decoder::<Item>::new(&json!({"type": 1}));
+
+    assert!(matches!(result, Err(JsonSchemaError::InvalidSchema(_))));
+  }
+
+  #[test]
+  fn test_invalid_json() {
+    let decoder = Decoder::<Item>::from_type().unwrap();
+
+    let result = decoder.decode(Bytes::from_static(b"{"));
+
+    assert!(matches!(result, Err(JsonSchemaError::Json(_))));
+  }
+}
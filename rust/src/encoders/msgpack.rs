@@ -31,13 +31,14 @@
 
 use ::std::marker::PhantomData;
 
-use ::bytes::Bytes;
+use ::bytes::{Bytes, BytesMut};
 use ::rmp_serde::{
   decode::{Error as DecodeError, from_slice},
-  encode::{Error as EncodeError, to_vec},
+  encode::{Error as EncodeError, to_vec, write},
 };
 use ::serde::{de::DeserializeOwned, ser::Serialize};
 
+use super::buf_writer::BufWriter;
 use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
 
 /// A MessagePack encoder for serializing data structures to MessagePack format.
@@ -143,6 +144,15 @@ impl<T: Serialize + Send + Sync> EncoderTrait for Encoder<T> {
   fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
     Ok(Bytes::from(to_vec(item)?))
   }
+
+  /// Encodes a value into MessagePack format at the end of `buf`.
+  fn encode_into(
+    &self,
+    item: &Self::Item,
+    buf: &mut BytesMut,
+  ) -> Result<(), Self::Error> {
+    write(&mut BufWriter(buf), item)
+  }
 }
 
 /// A MessagePack decoder for deserializing data structures from MessagePack format.
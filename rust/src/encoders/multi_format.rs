@@ -0,0 +1,419 @@
+//! Content-type negotiating encoder and decoder.
+//!
+//! This module helps migrating a topic from one format to another, when
+//! producers emitting different formats (e.g. JSON and MessagePack) share the
+//! same topic. The [`Decoder`] holds several registered decoders keyed by
+//! content type and picks one per message:
+//!
+//! 1. If the payload carries a format tag written by [`Encoder`], the decoder
+//!    registered for the tagged content type is used.
+//! 2. Otherwise the registered sniffers are tried in registration order, and
+//!    the first one recognizing the payload selects the decoder.
+//! 3. Otherwise the fallback content type is used, if any.
+//!
+//! When the content type is known from elsewhere (e.g. a message header),
+//! [`Decoder::decode_as`] skips the detection and dispatches directly.
+//!
+//! # Wire Format
+//!
+//! Tagged payloads start with the byte `0xC1`, followed by the length of the
+//! content type as a single byte, the ASCII content type itself, and the
+//! payload produced by the inner encoder. `0xC1` is reserved ("never used")
+//! in MessagePack and can't start a UTF-8 encoded JSON document, so tagged
+//! payloads are never mistaken for untagged JSON or MessagePack ones.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use serde::{Serialize, Deserialize};
+//! use object_transfer::encoders::{Encoder, Decoder};
+//! use object_transfer::encoders::{JSONEncoder, JSONDecoder};
+//! use object_transfer::encoders::{MessagePackEncoder, MessagePackDecoder};
+//! use object_transfer::encoders::multi_format::{
+//!   self, CONTENT_TYPE_JSON, CONTENT_TYPE_MSGPACK,
+//! };
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Event {
+//!     id: u32,
+//! }
+//!
+//! let decoder = multi_format::Decoder::new()
+//!   .format(CONTENT_TYPE_JSON, Arc::new(JSONDecoder::<Event>::new()))
+//!   .format(CONTENT_TYPE_MSGPACK, Arc::new(MessagePackDecoder::new()))
+//!   .sniff(CONTENT_TYPE_JSON, multi_format::looks_like_json)
+//!   .sniff(CONTENT_TYPE_MSGPACK, multi_format::looks_like_msgpack);
+//!
+//! // Legacy producers emit untagged JSON...
+//! let legacy = JSONEncoder::new().encode(&Event { id: 1 })?;
+//! // ...while migrated ones tag their MessagePack payloads.
+//! let tagged = multi_format::Encoder::new(
+//!   CONTENT_TYPE_MSGPACK,
+//!   Arc::new(MessagePackEncoder::new()),
+//! )?
+//! .encode(&Event { id: 2 })?;
+//!
+//! assert_eq!(decoder.decode(legacy)?, Event { id: 1 });
+//! assert_eq!(decoder.decode(tagged)?, Event { id: 2 });
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::error::Error as StdError;
+use ::std::sync::Arc;
+
+use ::bytes::{BufMut, Bytes, BytesMut};
+use ::thiserror::Error;
+
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// Content type of JSON payloads.
+pub const CONTENT_TYPE_JSON: &str = "application/json";
+/// Content type of MessagePack payloads.
+pub const CONTENT_TYPE_MSGPACK: &str = "application/msgpack";
+
+/// First byte of every tagged payload.
+const TAG_MAGIC: u8 = 0xC1;
+
+/// Error type for the multi-format encoder and decoder.
+#[derive(Error, Debug)]
+pub enum MultiFormatError {
+  /// The content type doesn't fit in the tag, which allows at most 255
+  /// bytes.
+  #[error("Content type is {0} bytes long, at most 255 are allowed")]
+  ContentTypeTooLong(usize),
+  /// No decoder is registered for the content type.
+  #[error("No decoder registered for content type {0:?}")]
+  UnknownContentType(String),
+  /// The payload is untagged, no sniffer recognized it and no fallback is
+  /// configured.
+  #[error("Unable to detect the payload format")]
+  UnrecognizedFormat,
+  /// The payload starts with the tag magic byte but the tag is truncated
+  /// or isn't valid UTF-8.
+  #[error("Malformed format tag")]
+  MalformedTag,
+  /// The decoder selected for the payload failed.
+  #[error("Decoding as {content_type:?} failed: {source}")]
+  Decode {
+    content_type: String,
+    source: Box<dyn StdError + Send + Sync>,
+  },
+}
+
+/// Returns `true` if the payload looks like a JSON object or array.
+pub fn looks_like_json(data: &[u8]) -> bool {
+  matches!(
+    data.iter().find(|b| !b.is_ascii_whitespace()),
+    Some(b'{') | Some(b'[')
+  )
+}
+
+/// Returns `true` if the payload looks like a MessagePack map or array.
+pub fn looks_like_msgpack(data: &[u8]) -> bool {
+  matches!(data.first(), Some(0x80..=0x9f) | Some(0xdc..=0xdf))
+}
+
+/// Encoder tagging the payloads of an inner encoder with their content type.
+///
+/// The tag lets [`Decoder`] dispatch to the right format without sniffing.
+/// Encoding errors are the ones of the inner encoder.
+pub struct Encoder<T, E: StdError + Send + Sync> {
+  tag: Bytes,
+  inner: Arc<dyn EncoderTrait<Item = T, Error = E> + Send + Sync>,
+}
+
+impl<T, E: StdError + Send + Sync> Encoder<T, E> {
+  /// Creates a new tagging encoder.
+  ///
+  /// # Parameters
+  /// - `content_type`: Content type written in the tag of every payload.
+  /// - `inner`: Encoder producing the tagged payload.
+  ///
+  /// # Errors
+  /// Returns [`MultiFormatError::ContentTypeTooLong`] if the content type is
+  /// longer than 255 bytes.
+  pub fn new(
+    content_type: &str,
+    inner: Arc<dyn EncoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> Result<Self, MultiFormatError> {
+    let len = u8::try_from(content_type.len())
+      .map_err(|_| MultiFormatError::ContentTypeTooLong(content_type.len()))?;
+    let mut tag = BytesMut::with_capacity(2 + content_type.len());
+    tag.put_u8(TAG_MAGIC);
+    tag.put_u8(len);
+    tag.put_slice(content_type.as_bytes());
+    Ok(Self {
+      tag: tag.freeze(),
+      inner,
+    })
+  }
+}
+
+impl<T, E> EncoderTrait for Encoder<T, E>
+where
+  T: Send + Sync,
+  E: StdError + Send + Sync,
+{
+  type Item = T;
+  type Error = E;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    let payload = self.inner.encode(item)?;
+    let mut buf = BytesMut::with_capacity(self.tag.len() + payload.len());
+    buf.put_slice(&self.tag);
+    buf.put_slice(&payload);
+    Ok(buf.freeze())
+  }
+}
+
+/// Sniffer recognizing untagged payloads of a given format.
+pub type Sniffer = fn(&[u8]) -> bool;
+
+type DecodeFn<T> = Box<
+  dyn Fn(Bytes) -> Result<T, Box<dyn StdError + Send + Sync>> + Send + Sync,
+>;
+
+/// Decoder dispatching to one of several registered decoders.
+///
+/// See the [module documentation](self) for the selection rules.
+pub struct Decoder<T> {
+  formats: Vec<(String, DecodeFn<T>)>,
+  sniffers: Vec<(String, Sniffer)>,
+  fallback: Option<String>,
+}
+
+impl<T: Send + Sync + 'static> Decoder<T> {
+  /// Creates a decoder without any registered format.
+  pub fn new() -> Self {
+    Self {
+      formats: Vec::new(),
+      sniffers: Vec::new(),
+      fallback: None,
+    }
+  }
+
+  /// Registers the decoder used for `content_type`.
+  ///
+  /// Registering the same content type twice replaces the previous decoder.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn format<E>(
+    mut self,
+    content_type: impl Into<String>,
+    decoder: Arc<dyn DecoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> Self
+  where
+    E: StdError + Send + Sync + 'static,
+  {
+    let content_type = content_type.into();
+    let decode: DecodeFn<T> = Box::new(move |data| {
+      decoder
+        .decode(data)
+        .map_err(|e| Box::new(e) as Box<dyn StdError + Send + Sync>)
+    });
+    self.formats.retain(|(ct, _)| *ct != content_type);
+    self.formats.push((content_type, decode));
+    self
+  }
+
+  /// Registers a sniffer selecting `content_type` for untagged payloads.
+  ///
+  /// Sniffers are tried in registration order.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn sniff(
+    mut self,
+    content_type: impl Into<String>,
+    sniffer: Sniffer,
+  ) -> Self {
+    self.sniffers.push((content_type.into(), sniffer));
+    self
+  }
+
+  /// Sets the content type used when the payload is untagged and no sniffer
+  /// recognizes it.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn fallback(mut self, content_type: impl Into<String>) -> Self {
+    self.fallback = Some(content_type.into());
+    self
+  }
+
+  /// Decodes an untagged payload with the decoder registered for
+  /// `content_type`.
+  ///
+  /// # Parameters
+  /// - `content_type`: Content type of the payload, e.g. from a header.
+  /// - `data`: The payload without any format tag.
+  pub fn decode_as(
+    &self,
+    content_type: &str,
+    data: Bytes,
+  ) -> Result<T, MultiFormatError> {
+    let (_, decode) = self
+      .formats
+      .iter()
+      .find(|(ct, _)| ct == content_type)
+      .ok_or_else(|| {
+      MultiFormatError::UnknownContentType(content_type.to_string())
+    })?;
+    decode(data).map_err(|source| MultiFormatError::Decode {
+      content_type: content_type.to_string(),
+      source,
+    })
+  }
+}
+
+impl<T: Send + Sync + 'static> Default for Decoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Send + Sync + 'static> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = MultiFormatError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    if data.first() == Some(&TAG_MAGIC) {
+      let len = *data.get(1).ok_or(MultiFormatError::MalformedTag)? as usize;
+      let content_type = data
+        .get(2..2 + len)
+        .and_then(|ct| std::str::from_utf8(ct).ok())
+        .ok_or(MultiFormatError::MalformedTag)?;
+      return self.decode_as(content_type, data.slice(2 + len..));
+    }
+    let content_type = self
+      .sniffers
+      .iter()
+      .find(|(_, sniffer)| sniffer(&data))
+      .map(|(ct, _)| ct)
+      .or(self.fallback.as_ref())
+      .ok_or(MultiFormatError::UnrecognizedFormat)?;
+    self.decode_as(content_type, data)
+  }
+}
+
+#[cfg(all(test, feature = "json", feature = "msgpack"))]
+mod test {
+  use crate::encoders::{
+    JSONDecoder, JSONEncoder, MessagePackDecoder, MessagePackEncoder,
+  };
+  use crate::tests::entity::TestEntity;
+
+  use super::*;
+
+  fn decoder() -> Decoder<TestEntity> {
+    Decoder::new()
+      .format(CONTENT_TYPE_JSON, Arc::new(JSONDecoder::new()))
+      .format(CONTENT_TYPE_MSGPACK, Arc::new(MessagePackDecoder::new()))
+  }
+
+  #[test]
+  fn test_tagged_roundtrip() {
+    let entity = TestEntity::new(1, "tagged");
+    let json =
+      Encoder::new(CONTENT_TYPE_JSON, Arc::new(JSONEncoder::new())).unwrap();
+    let msgpack =
+      Encoder::new(CONTENT_TYPE_MSGPACK, Arc::new(MessagePackEncoder::new()))
+        .unwrap();
+    let decoder = decoder();
+
+    let from_json = decoder.decode(json.encode(&entity).unwrap()).unwrap();
+    let from_msgpack =
+      decoder.decode(msgpack.encode(&entity).unwrap()).unwrap();
+
+    assert_eq!(from_json, entity);
+    assert_eq!(from_msgpack, entity);
+  }
+
+  #[test]
+  fn test_tag_layout() {
+    let encoder = Encoder::new("a/b", Arc::new(JSONEncoder::new())).unwrap();
+
+    let encoded = encoder.encode(&TestEntity::new(1, "x")).unwrap();
+
+    assert_eq!(&encoded[..5], &[TAG_MAGIC, 3, b'a', b'/', b'b']);
+    assert_eq!(encoded[5], b'{');
+  }
+
+  #[test]
+  fn test_content_type_too_long() {
+    let content_type = "a".repeat(256);
+
+    let result = Encoder::<TestEntity, _>::new(
+      &content_type,
+      Arc::new(JSONEncoder::new()),
+    );
+
+    assert!(matches!(
+      result,
+      Err(MultiFormatError::ContentTypeTooLong(256))
+    ));
+  }
+
+  #[test]
+  fn test_sniffed_roundtrip() {
+    let entity = TestEntity::new(2, "sniffed");
+    let decoder = decoder()
+      .sniff(CONTENT_TYPE_JSON, looks_like_json)
+      .sniff(CONTENT_TYPE_MSGPACK, looks_like_msgpack);
+
+    let json = JSONEncoder::new().encode(&entity).unwrap();
+    let msgpack = MessagePackEncoder::new().encode(&entity).unwrap();
+
+    assert_eq!(decoder.decode(json).unwrap(), entity);
+    assert_eq!(decoder.decode(msgpack).unwrap(), entity);
+  }
+
+  #[test]
+  fn test_fallback() {
+    let entity = TestEntity::new(3, "fallback");
+    let decoder = decoder().fallback(CONTENT_TYPE_MSGPACK);
+
+    let msgpack = MessagePackEncoder::new().encode(&entity).unwrap();
+
+    assert_eq!(decoder.decode(msgpack).unwrap(), entity);
+  }
+
+  #[test]
+  fn test_unrecognized_format() {
+    let result = decoder().decode(Bytes::from_static(b"plain text"));
+
+    assert!(matches!(result, Err(MultiFormatError::UnrecognizedFormat)));
+  }
+
+  #[test]
+  fn test_unknown_content_type() {
+    let encoder =
+      Encoder::new("application/unknown", Arc::new(JSONEncoder::new()))
+        .unwrap();
+    let payload = encoder.encode(&TestEntity::new(1, "x")).unwrap();
+
+    let result = decoder().decode(payload);
+
+    assert!(matches!(
+      result,
+      Err(MultiFormatError::UnknownContentType(ct)) if ct == "application/unknown"
+    ));
+  }
+
+  #[test]
+  fn test_malformed_tag() {
+    let result = decoder().decode(Bytes::from_static(&[TAG_MAGIC, 10, b'a']));
+
+    assert!(matches!(result, Err(MultiFormatError::MalformedTag)));
+  }
+
+  #[test]
+  fn test_decode_error() {
+    let result =
+      decoder().decode_as(CONTENT_TYPE_JSON, Bytes::from_static(b"{"));
+
+    assert!(matches!(result, Err(MultiFormatError::Decode { .. })));
+  }
+}
@@ -0,0 +1,183 @@
+//! Postcard encoder and decoder implementations.
+//!
+//! This module provides `Encoder` and `Decoder` trait implementations for the
+//! [postcard](https://docs.rs/postcard) binary format. Postcard produces the
+//! smallest payloads among the built-in formats by using variable-length
+//! integers and omitting field names, which makes it well suited for
+//! high-volume Rust-to-Rust traffic.
+//!
+//! # Example
+//!
+//! ```rust
+//! use object_transfer::encoders::{Encoder, Decoder};
+//! use object_transfer::encoders::postcard::{
+//!   Encoder as PostcardEncoder,
+//!   Decoder as PostcardDecoder,
+//! };
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Message {
+//!     id: u32,
+//!     content: String,
+//! }
+//!
+//! let encoder = PostcardEncoder::new();
+//! let decoder = PostcardDecoder::new();
+//!
+//! let msg = Message { id: 1, content: "Hello".to_string() };
+//! let encoded = encoder.encode(&msg)?;
+//! let decoded: Message = decoder.decode(encoded)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::marker::PhantomData;
+
+use ::bytes::{Bytes, BytesMut};
+use ::postcard::{Error, from_bytes, to_allocvec, to_io};
+use ::serde::{de::DeserializeOwned, ser::Serialize};
+
+use super::buf_writer::BufWriter;
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// A postcard encoder for serializing data structures to postcard format.
+///
+/// `Encoder<T>` implements the [`Encoder`](super::traits::Encoder) trait to
+/// provide postcard serialization for any type `T` that implements
+/// [`serde::Serialize`].
+///
+/// # Type Parameters
+///
+/// * `T` - The data type to be encoded. Must implement [`Serialize`],
+///   [`Send`], and [`Sync`].
+#[derive(Debug)]
+pub struct Encoder<T: Serialize + Send + Sync> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: Serialize + Send + Sync> Encoder<T> {
+  /// Creates a new postcard encoder.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: Serialize + Send + Sync> Default for Encoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Serialize + Send + Sync> EncoderTrait for Encoder<T> {
+  type Item = T;
+  type Error = Error;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    Ok(Bytes::from(to_allocvec(item)?))
+  }
+
+  fn encode_into(
+    &self,
+    item: &Self::Item,
+    buf: &mut BytesMut,
+  ) -> Result<(), Self::Error> {
+    to_io(item, BufWriter(buf))?;
+    Ok(())
+  }
+}
+
+/// A postcard decoder for deserializing data structures from postcard format.
+///
+/// `Decoder<T>` implements the [`Decoder`](super::traits::Decoder) trait to
+/// provide postcard deserialization for any type `T` that implements
+/// [`serde::Deserialize`].
+///
+/// # Type Parameters
+///
+/// * `T` - The data type to be decoded. Must implement [`DeserializeOwned`],
+///   [`Send`], and [`Sync`].
+#[derive(Debug)]
+pub struct Decoder<T: DeserializeOwned + Send + Sync> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned + Send + Sync> Decoder<T> {
+  /// Creates a new postcard decoder.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: DeserializeOwned + Send + Sync> Default for Decoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: DeserializeOwned + Send + Sync> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = Error;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    from_bytes(&data)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::tests::entity::TestEntity;
+
+  use super::*;
+
+  #[test]
+  fn test_encode_decode_roundtrip() {
+    let encoder = Encoder::new();
+    let decoder = Decoder::new();
+
+    let original = TestEntity::new(123, "roundtrip_test");
+
+    let encoded = encoder.encode(&original).expect("encoding failed");
+    let decoded: TestEntity =
+      decoder.decode(encoded).expect("decoding failed");
+
+    assert_eq!(decoded, original);
+  }
+
+  #[test]
+  fn test_postcard_encoder_wire_format() {
+    let encoder = Encoder::new();
+
+    let encoded = encoder
+      .encode(&TestEntity::new(1, "a"))
+      .expect("encoding failed");
+
+    // varint(1), then the string length and bytes.
+    assert_eq!(encoded.as_ref(), &[0x01, 0x01, b'a']);
+  }
+
+  #[test]
+  fn test_postcard_decoder_truncated_payload() {
+    let decoder = Decoder::<TestEntity>::new();
+
+    let result = decoder.decode(Bytes::from_static(&[0x01, 0x05, b'a']));
+
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn test_encode_into_matches_encode() {
+    let encoder = Encoder::new();
+    let entity = TestEntity::new(1, "buffered");
+    let mut buf = BytesMut::new();
+
+    encoder
+      .encode_into(&entity, &mut buf)
+      .expect("encoding failed");
+
+    assert_eq!(buf.freeze(), encoder.encode(&entity).unwrap());
+  }
+}
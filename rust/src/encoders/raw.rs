@@ -0,0 +1,213 @@
+//! Passthrough encoder and decoder for raw bytes and text.
+//!
+//! Bridges and tooling often move opaque blobs or UTF-8 text without knowing
+//! their format. The [`Encoder`] and [`Decoder`] of this module publish
+//! [`Bytes`], `Vec<u8>` and [`String`] items as-is, bypassing serde, so that
+//! they can be used with the typed [`Pub`](crate::Pub) and
+//! [`Sub`](crate::Sub) (see [`Pub::raw`](crate::Pub::raw) and
+//! [`Sub::raw`](crate::Sub::raw)).
+//!
+//! # Example
+//!
+//! ```rust
+//! use bytes::Bytes;
+//! use object_transfer::encoders::{Decoder, Encoder};
+//! use object_transfer::encoders::raw;
+//!
+//! let encoded = raw::Encoder::new().encode(&"hello".to_string())?;
+//! assert_eq!(encoded, Bytes::from_static(b"hello"));
+//!
+//! let decoded: String = raw::Decoder::new().decode(encoded)?;
+//! assert_eq!(decoded, "hello");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::convert::Infallible;
+use ::std::marker::PhantomData;
+use ::std::string::FromUtf8Error;
+
+use ::bytes::{Bytes, BytesMut};
+use ::thiserror::Error;
+
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// Error type for the raw decoder.
+#[derive(Error, Debug)]
+pub enum RawError {
+  /// The payload isn't valid UTF-8 text.
+  #[error("Invalid UTF-8: {0}")]
+  Utf8(#[from] FromUtf8Error),
+}
+
+/// Item types published as-is by the raw codec.
+pub trait RawItem: Sized + Send + Sync {
+  /// Returns the content of the item.
+  fn as_slice(&self) -> &[u8];
+
+  /// Converts the item into a payload.
+  fn to_bytes(&self) -> Bytes {
+    Bytes::copy_from_slice(self.as_slice())
+  }
+
+  /// Converts a payload into an item.
+  fn from_bytes(data: Bytes) -> Result<Self, RawError>;
+}
+
+impl RawItem for Bytes {
+  fn as_slice(&self) -> &[u8] {
+    self
+  }
+
+  /// Returns a clone of the item, without copying its content.
+  fn to_bytes(&self) -> Bytes {
+    self.clone()
+  }
+
+  fn from_bytes(data: Bytes) -> Result<Self, RawError> {
+    Ok(data)
+  }
+}
+
+impl RawItem for Vec<u8> {
+  fn as_slice(&self) -> &[u8] {
+    self
+  }
+
+  fn from_bytes(data: Bytes) -> Result<Self, RawError> {
+    Ok(Vec::from(data))
+  }
+}
+
+impl RawItem for String {
+  fn as_slice(&self) -> &[u8] {
+    self.as_bytes()
+  }
+
+  fn from_bytes(data: Bytes) -> Result<Self, RawError> {
+    Ok(String::from_utf8(Vec::from(data))?)
+  }
+}
+
+/// Encoder publishing raw items as-is.
+///
+/// # Type Parameters
+///
+/// * `T` - [`Bytes`], `Vec<u8>`, [`String`], or another [`RawItem`].
+#[derive(Debug)]
+pub struct Encoder<T: RawItem> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: RawItem> Encoder<T> {
+  /// Creates a new raw encoder.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: RawItem> Default for Encoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: RawItem> EncoderTrait for Encoder<T> {
+  type Item = T;
+  type Error = Infallible;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    Ok(item.to_bytes())
+  }
+
+  fn encode_into(
+    &self,
+    item: &Self::Item,
+    buf: &mut BytesMut,
+  ) -> Result<(), Self::Error> {
+    buf.extend_from_slice(item.as_slice());
+    Ok(())
+  }
+}
+
+/// Decoder receiving payloads as raw items.
+///
+/// Decoding only fails when receiving a [`String`] that isn't valid UTF-8.
+///
+/// # Type Parameters
+///
+/// * `T` - [`Bytes`], `Vec<u8>`, [`String`], or another [`RawItem`].
+#[derive(Debug)]
+pub struct Decoder<T: RawItem> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: RawItem> Decoder<T> {
+  /// Creates a new raw decoder.
+  pub fn new() -> Self {
+    Self {
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: RawItem> Default for Decoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: RawItem> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = RawError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    T::from_bytes(data)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_bytes_are_not_copied() {
+    let data = Bytes::from_static(b"blob");
+
+    let encoded = Encoder::new().encode(&data).unwrap();
+    let decoded: Bytes = Decoder::new().decode(encoded.clone()).unwrap();
+
+    assert_eq!(encoded.as_ptr(), data.as_ptr());
+    assert_eq!(decoded.as_ptr(), data.as_ptr());
+  }
+
+  #[test]
+  fn test_vec_round_trip() {
+    let data = vec![0, 159, 146, 150];
+
+    let encoded = Encoder::new().encode(&data).unwrap();
+    let decoded: Vec<u8> = Decoder::new().decode(encoded).unwrap();
+
+    assert_eq!(decoded, data);
+  }
+
+  #[test]
+  fn test_string_round_trip() {
+    let data = "こんにちは".to_string();
+    let mut buf = BytesMut::new();
+
+    Encoder::new().encode_into(&data, &mut buf).unwrap();
+    let decoded: String = Decoder::new().decode(buf.freeze()).unwrap();
+
+    assert_eq!(decoded, data);
+  }
+
+  #[test]
+  fn test_invalid_utf8() {
+    let result =
+      Decoder::<String>::new().decode(Bytes::from_static(&[0, 159, 146]));
+
+    assert!(matches!(result, Err(RawError::Utf8(_))));
+  }
+}
@@ -0,0 +1,340 @@
+//! Registry of encoders and decoders keyed by format name.
+//!
+//! Services selecting their format from configuration would otherwise need a
+//! hand-written `match` from format names to encoders and decoders. A
+//! [`CodecRegistry`] maps names such as `"json"` or `"msgpack"` to factories
+//! creating the encoder and decoder of a given item type. The created codecs
+//! report their errors as [`CodecError`], so that [`Pub`](crate::Pub) and
+//! [`Sub`](crate::Sub) have the same type whatever the selected format.
+//!
+//! [`CodecRegistry::builtin`] registers the built-in serde formats enabled
+//! by features. Other formats (e.g. CBOR, or a custom binary protocol) are
+//! registered with [`CodecRegistry::encoder`] and
+//! [`CodecRegistry::decoder`].
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::sync::Arc;
+//! use serde::{Deserialize, Serialize};
+//! use object_transfer::{Pub, traits::PubTrait};
+//! use object_transfer::encoders::{CodecError, CodecRegistry};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!   id: u32,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!   let format = std::env::var("EVENT_FORMAT")?;
+//!   let registry = CodecRegistry::<Event>::builtin();
+//!
+//!   let client = async_nats::connect("demo.nats.io").await?;
+//!   let js = Arc::new(async_nats::jetstream::new(client));
+//!   let publisher: Pub<Event, CodecError> =
+//!     Pub::new(js, "events", registry.make_encoder(&format)?);
+//!
+//!   publisher.publish(&Event { id: 1 }).await?;
+//!   Ok(())
+//! }
+//! ```
+
+use ::std::collections::BTreeMap;
+use ::std::error::Error as StdError;
+use ::std::sync::Arc;
+
+use ::bytes::{Bytes, BytesMut};
+#[cfg(any(
+  feature = "json",
+  feature = "msgpack",
+  feature = "bincode",
+  feature = "postcard"
+))]
+use ::serde::{Serialize, de::DeserializeOwned};
+use ::thiserror::Error;
+
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// Error of the encoders and decoders created by a [`CodecRegistry`].
+///
+/// Wraps the error of the underlying format.
+#[derive(Error, Debug)]
+#[error(transparent)]
+pub struct CodecError(#[from] Box<dyn StdError + Send + Sync>);
+
+impl CodecError {
+  /// Creates a new CodecError from any error that implements StdError.
+  pub fn new<E>(err: E) -> Self
+  where
+    E: StdError + Send + Sync + 'static,
+  {
+    Self(Box::new(err))
+  }
+}
+
+/// Error type for registry lookups.
+#[derive(Error, Debug)]
+pub enum CodecRegistryError {
+  /// No encoder is registered under the name.
+  #[error("No encoder registered for format: {0}")]
+  UnknownEncoder(String),
+  /// No decoder is registered under the name.
+  #[error("No decoder registered for format: {0}")]
+  UnknownDecoder(String),
+}
+
+/// Encoder created by a [`CodecRegistry`].
+pub type DynEncoder<T> =
+  Arc<dyn EncoderTrait<Item = T, Error = CodecError> + Send + Sync>;
+
+/// Decoder created by a [`CodecRegistry`].
+pub type DynDecoder<T> =
+  Arc<dyn DecoderTrait<Item = T, Error = CodecError> + Send + Sync>;
+
+type EncoderFactory<T> = Box<dyn Fn() -> DynEncoder<T> + Send + Sync>;
+type DecoderFactory<T> = Box<dyn Fn() -> DynDecoder<T> + Send + Sync>;
+
+/// Codec reporting the errors of the wrapped codec as [`CodecError`].
+struct Erased<C>(C);
+
+impl<C> EncoderTrait for Erased<C>
+where
+  C: EncoderTrait,
+  C::Error: 'static,
+{
+  type Item = C::Item;
+  type Error = CodecError;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    self.0.encode(item).map_err(CodecError::new)
+  }
+
+  fn encode_into(
+    &self,
+    item: &Self::Item,
+    buf: &mut BytesMut,
+  ) -> Result<(), Self::Error> {
+    self.0.encode_into(item, buf).map_err(CodecError::new)
+  }
+}
+
+impl<C> DecoderTrait for Erased<C>
+where
+  C: DecoderTrait,
+  C::Error: 'static,
+{
+  type Item = C::Item;
+  type Error = CodecError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    self.0.decode(data).map_err(CodecError::new)
+  }
+}
+
+/// Registry of encoder and decoder factories keyed by format name.
+///
+/// # Type Parameters
+///
+/// * `T` - The item type encoded and decoded by the registered codecs.
+pub struct CodecRegistry<T> {
+  encoders: BTreeMap<String, EncoderFactory<T>>,
+  decoders: BTreeMap<String, DecoderFactory<T>>,
+}
+
+impl<T: Send + Sync + 'static> CodecRegistry<T> {
+  /// Creates an empty registry.
+  pub fn new() -> Self {
+    Self {
+      encoders: BTreeMap::new(),
+      decoders: BTreeMap::new(),
+    }
+  }
+
+  /// Registers the factory of the encoder named `name`.
+  ///
+  /// Registering the same name twice replaces the previous factory.
+  ///
+  /// # Parameters
+  /// - `name`: The format name, e.g. `"cbor"`.
+  /// - `factory`: Creates the encoder, e.g. `JSONEncoder::new`.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn encoder<C, F>(mut self, name: impl Into<String>, factory: F) -> Self
+  where
+    C: EncoderTrait<Item = T> + Send + Sync + 'static,
+    C::Error: 'static,
+    F: Fn() -> C + Send + Sync + 'static,
+  {
+    let factory: EncoderFactory<T> =
+      Box::new(move || Arc::new(Erased(factory())));
+    self.encoders.insert(name.into(), factory);
+    self
+  }
+
+  /// Registers the factory of the decoder named `name`.
+  ///
+  /// Registering the same name twice replaces the previous factory.
+  ///
+  /// # Parameters
+  /// - `name`: The format name, e.g. `"cbor"`.
+  /// - `factory`: Creates the decoder, e.g. `JSONDecoder::new`.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn decoder<C, F>(mut self, name: impl Into<String>, factory: F) -> Self
+  where
+    C: DecoderTrait<Item = T> + Send + Sync + 'static,
+    C::Error: 'static,
+    F: Fn() -> C + Send + Sync + 'static,
+  {
+    let factory: DecoderFactory<T> =
+      Box::new(move || Arc::new(Erased(factory())));
+    self.decoders.insert(name.into(), factory);
+    self
+  }
+
+  /// Creates the encoder named `name`.
+  pub fn make_encoder(
+    &self,
+    name: &str,
+  ) -> Result<DynEncoder<T>, CodecRegistryError> {
+    self
+      .encoders
+      .get(name)
+      .map(|factory| factory())
+      .ok_or_else(|| CodecRegistryError::UnknownEncoder(name.to_string()))
+  }
+
+  /// Creates the decoder named `name`.
+  pub fn make_decoder(
+    &self,
+    name: &str,
+  ) -> Result<DynDecoder<T>, CodecRegistryError> {
+    self
+      .decoders
+      .get(name)
+      .map(|factory| factory())
+      .ok_or_else(|| CodecRegistryError::UnknownDecoder(name.to_string()))
+  }
+
+  /// Returns the names of the registered encoders, in alphabetical order.
+  pub fn encoder_names(&self) -> impl Iterator<Item = &str> {
+    self.encoders.keys().map(String::as_str)
+  }
+
+  /// Returns the names of the registered decoders, in alphabetical order.
+  pub fn decoder_names(&self) -> impl Iterator<Item = &str> {
+    self.decoders.keys().map(String::as_str)
+  }
+}
+
+#[cfg(any(
+  feature = "json",
+  feature = "msgpack",
+  feature = "bincode",
+  feature = "postcard"
+))]
+impl<T> CodecRegistry<T>
+where
+  T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+  /// Creates a registry of the built-in serde formats enabled by features:
+  /// `"json"`, `"msgpack"`, `"bincode"` and `"postcard"`.
+  pub fn builtin() -> Self {
+    let registry = Self::new();
+    #[cfg(feature = "json")]
+    let registry = registry
+      .encoder("json", super::JSONEncoder::new)
+      .decoder("json", super::JSONDecoder::new);
+    #[cfg(feature = "msgpack")]
+    let registry = registry
+      .encoder("msgpack", super::MessagePackEncoder::new)
+      .decoder("msgpack", super::MessagePackDecoder::new);
+    #[cfg(feature = "bincode")]
+    let registry = registry
+      .encoder("bincode", super::BincodeEncoder::new)
+      .decoder("bincode", super::BincodeDecoder::new);
+    #[cfg(feature = "postcard")]
+    let registry = registry
+      .encoder("postcard", super::PostcardEncoder::new)
+      .decoder("postcard", super::PostcardDecoder::new);
+    registry
+  }
+}
+
+impl<T: Send + Sync + 'static> Default for CodecRegistry<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::encoders::raw;
+  use crate::tests::entity::TestEntity;
+
+  #[test]
+  fn test_custom_format() {
+    let registry = CodecRegistry::<String>::new()
+      .encoder("text", raw::Encoder::new)
+      .decoder("text", raw::Decoder::new);
+
+    let encoder = registry.make_encoder("text").unwrap();
+    let decoder = registry.make_decoder("text").unwrap();
+    let encoded = encoder.encode(&"hello".to_string()).unwrap();
+
+    assert_eq!(decoder.decode(encoded).unwrap(), "hello");
+  }
+
+  #[test]
+  fn test_unknown_format() {
+    let registry = CodecRegistry::<TestEntity>::new();
+
+    assert!(matches!(
+      registry.make_encoder("cbor"),
+      Err(CodecRegistryError::UnknownEncoder(name)) if name == "cbor"
+    ));
+    assert!(matches!(
+      registry.make_decoder("cbor"),
+      Err(CodecRegistryError::UnknownDecoder(name)) if name == "cbor"
+    ));
+  }
+
+  #[test]
+  fn test_errors_are_erased() {
+    let registry =
+      CodecRegistry::<String>::new().decoder("text", raw::Decoder::new);
+
+    let result = registry
+      .make_decoder("text")
+      .unwrap()
+      .decode(Bytes::from_static(&[0xFF]));
+
+    assert!(result.is_err_and(|err| err.to_string().contains("UTF-8")));
+  }
+
+  #[cfg(all(feature = "json", feature = "msgpack"))]
+  #[test]
+  fn test_builtin() {
+    let registry = CodecRegistry::<TestEntity>::builtin();
+    let entity = TestEntity::new(1, "builtin");
+
+    for name in ["json", "msgpack"] {
+      let encoded = registry
+        .make_encoder(name)
+        .unwrap()
+        .encode(&entity)
+        .unwrap();
+      let decoded = registry
+        .make_decoder(name)
+        .unwrap()
+        .decode(encoded)
+        .unwrap();
+      assert_eq!(decoded, entity);
+    }
+    assert!(registry.encoder_names().any(|name| name == "json"));
+  }
+}
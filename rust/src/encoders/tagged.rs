@@ -0,0 +1,407 @@
+//! Type-tagged envelope encoder and decoder for polymorphic topics.
+//!
+//! When many event kinds share one topic, subscribers usually consume an enum
+//! with one variant per kind. The [`Encoder`] prefixes each payload with the
+//! type tag of the variant (see [`TypeTag`]) and encodes the variant with the
+//! encoder registered for that tag; the [`Decoder`] reads the tag back and
+//! decodes the payload with the decoder registered for it. Payloads with a
+//! tag nobody registered are reported as [`TaggedError::UnknownType`] rather
+//! than as a generic decoding failure.
+//!
+//! Decoded items can be routed to per-type async handlers with
+//! [`Dispatcher`](crate::Dispatcher).
+//!
+//! # Wire Format
+//!
+//! | Bytes        | Content                                  |
+//! |--------------|------------------------------------------|
+//! | 0            | Length `n` of the type tag               |
+//! | 1..1+n       | UTF-8 type tag                           |
+//! | 1+n..        | Payload produced by the variant encoder  |
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use serde::{Serialize, Deserialize};
+//! use object_transfer::encoders::{Encoder, Decoder};
+//! use object_transfer::encoders::{JSONEncoder, JSONDecoder};
+//! use object_transfer::encoders::tagged::{self, TypeTag};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Created { id: u32 }
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct Deleted { id: u32 }
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Event {
+//!     Created(Created),
+//!     Deleted(Deleted),
+//! }
+//!
+//! impl TypeTag for Event {
+//!     fn type_tag(&self) -> &str {
+//!         match self {
+//!             Event::Created(_) => "created",
+//!             Event::Deleted(_) => "deleted",
+//!         }
+//!     }
+//! }
+//!
+//! let encoder = tagged::Encoder::new()
+//!   .variant("created", Arc::new(JSONEncoder::new()), |e: &Event| match e {
+//!     Event::Created(v) => Some(v),
+//!     _ => None,
+//!   })
+//!   .variant("deleted", Arc::new(JSONEncoder::new()), |e: &Event| match e {
+//!     Event::Deleted(v) => Some(v),
+//!     _ => None,
+//!   });
+//! let decoder = tagged::Decoder::new()
+//!   .variant("created", Arc::new(JSONDecoder::new()), Event::Created)
+//!   .variant("deleted", Arc::new(JSONDecoder::new()), Event::Deleted);
+//!
+//! let event = Event::Deleted(Deleted { id: 1 });
+//! assert_eq!(decoder.decode(encoder.encode(&event)?)?, event);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::collections::HashMap;
+use ::std::error::Error as StdError;
+use ::std::sync::Arc;
+
+use ::bytes::{BufMut, Bytes, BytesMut};
+use ::thiserror::Error;
+
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// Provides the type discriminator of an item.
+///
+/// Usually implemented by an enum with one variant per event kind, returning
+/// a distinct tag per variant.
+pub trait TypeTag {
+  /// Returns the type tag written in the envelope.
+  fn type_tag(&self) -> &str;
+}
+
+/// Error type for the type-tagged encoder and decoder.
+#[derive(Error, Debug)]
+pub enum TaggedError {
+  /// No variant is registered for the type tag.
+  #[error("Unknown type: {0:?}")]
+  UnknownType(String),
+  /// The extractor registered for the type tag didn't accept the item.
+  #[error("Item tagged {0:?} doesn't match the registered variant")]
+  VariantMismatch(String),
+  /// The type tag is longer than 255 bytes.
+  #[error("Type tag too long: {0} bytes")]
+  TagTooLong(usize),
+  /// The envelope header is truncated or the tag isn't valid UTF-8.
+  #[error("Malformed type tag header")]
+  MalformedHeader,
+  /// The variant encoder failed.
+  #[error("Encoding type {type_tag:?} failed: {source}")]
+  Encode {
+    type_tag: String,
+    source: Box<dyn StdError + Send + Sync>,
+  },
+  /// The variant decoder failed.
+  #[error("Decoding type {type_tag:?} failed: {source}")]
+  Decode {
+    type_tag: String,
+    source: Box<dyn StdError + Send + Sync>,
+  },
+}
+
+type EncodeFn<T> = Box<dyn Fn(&T) -> Result<Bytes, TaggedError> + Send + Sync>;
+type DecodeFn<T> = Box<dyn Fn(Bytes) -> Result<T, TaggedError> + Send + Sync>;
+
+/// Encoder writing type-tagged envelopes.
+///
+/// Each variant is registered with the encoder of its payload and a function
+/// extracting the payload from the item.
+pub struct Encoder<T: TypeTag> {
+  variants: HashMap<String, EncodeFn<T>>,
+}
+
+impl<T: TypeTag + Send + Sync + 'static> Encoder<T> {
+  /// Creates an encoder without any registered variant.
+  pub fn new() -> Self {
+    Self {
+      variants: HashMap::new(),
+    }
+  }
+
+  /// Registers the variant tagged `type_tag`.
+  ///
+  /// # Parameters
+  /// - `type_tag`: Type tag of the variant, as returned by
+  ///   [`TypeTag::type_tag`].
+  /// - `encoder`: Encoder for the payload of the variant.
+  /// - `extract`: Returns the payload of the variant, or `None` if the item
+  ///   is another variant.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn variant<V, E, F>(
+    mut self,
+    type_tag: impl Into<String>,
+    encoder: Arc<dyn EncoderTrait<Item = V, Error = E> + Send + Sync>,
+    extract: F,
+  ) -> Self
+  where
+    V: Send + Sync + 'static,
+    E: StdError + Send + Sync + 'static,
+    F: Fn(&T) -> Option<&V> + Send + Sync + 'static,
+  {
+    let type_tag = type_tag.into();
+    let tag = type_tag.clone();
+    let encode: EncodeFn<T> = Box::new(move |item| {
+      let variant = extract(item)
+        .ok_or_else(|| TaggedError::VariantMismatch(tag.clone()))?;
+      encoder.encode(variant).map_err(|e| TaggedError::Encode {
+        type_tag: tag.clone(),
+        source: Box::new(e),
+      })
+    });
+    self.variants.insert(type_tag, encode);
+    self
+  }
+}
+
+impl<T: TypeTag + Send + Sync + 'static> Default for Encoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: TypeTag + Send + Sync + 'static> EncoderTrait for Encoder<T> {
+  type Item = T;
+  type Error = TaggedError;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    let type_tag = item.type_tag();
+    let encode = self
+      .variants
+      .get(type_tag)
+      .ok_or_else(|| TaggedError::UnknownType(type_tag.to_string()))?;
+    let len = u8::try_from(type_tag.len())
+      .map_err(|_| TaggedError::TagTooLong(type_tag.len()))?;
+    let payload = encode(item)?;
+    let mut buf = BytesMut::with_capacity(1 + type_tag.len() + payload.len());
+    buf.put_u8(len);
+    buf.put_slice(type_tag.as_bytes());
+    buf.put_slice(&payload);
+    Ok(buf.freeze())
+  }
+}
+
+/// Decoder reading type-tagged envelopes.
+///
+/// Each variant is registered with the decoder of its payload and a function
+/// wrapping the payload into the item, typically the enum variant itself.
+pub struct Decoder<T> {
+  variants: HashMap<String, DecodeFn<T>>,
+}
+
+impl<T: Send + Sync + 'static> Decoder<T> {
+  /// Creates a decoder without any registered variant.
+  pub fn new() -> Self {
+    Self {
+      variants: HashMap::new(),
+    }
+  }
+
+  /// Registers the variant tagged `type_tag`.
+  ///
+  /// # Parameters
+  /// - `type_tag`: Type tag of the variant.
+  /// - `decoder`: Decoder for the payload of the variant.
+  /// - `wrap`: Wraps the decoded payload into the item.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn variant<V, E, F>(
+    mut self,
+    type_tag: impl Into<String>,
+    decoder: Arc<dyn DecoderTrait<Item = V, Error = E> + Send + Sync>,
+    wrap: F,
+  ) -> Self
+  where
+    V: Send + Sync + 'static,
+    E: StdError + Send + Sync + 'static,
+    F: Fn(V) -> T + Send + Sync + 'static,
+  {
+    let type_tag = type_tag.into();
+    let tag = type_tag.clone();
+    let decode: DecodeFn<T> = Box::new(move |data| {
+      decoder
+        .decode(data)
+        .map(&wrap)
+        .map_err(|e| TaggedError::Decode {
+          type_tag: tag.clone(),
+          source: Box::new(e),
+        })
+    });
+    self.variants.insert(type_tag, decode);
+    self
+  }
+}
+
+impl<T: Send + Sync + 'static> Default for Decoder<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Send + Sync + 'static> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = TaggedError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    let len = *data.first().ok_or(TaggedError::MalformedHeader)? as usize;
+    let type_tag = data
+      .get(1..1 + len)
+      .and_then(|tag| std::str::from_utf8(tag).ok())
+      .ok_or(TaggedError::MalformedHeader)?;
+    let decode = self
+      .variants
+      .get(type_tag)
+      .ok_or_else(|| TaggedError::UnknownType(type_tag.to_string()))?;
+    decode(data.slice(1 + len..))
+  }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+  use crate::encoders::{JSONDecoder, JSONEncoder};
+  use crate::tests::entity::TestEntity;
+
+  use super::*;
+
+  #[derive(Debug, PartialEq)]
+  enum Event {
+    Created(TestEntity),
+    Deleted(u32),
+  }
+
+  impl TypeTag for Event {
+    fn type_tag(&self) -> &str {
+      match self {
+        Event::Created(_) => "created",
+        Event::Deleted(_) => "deleted",
+      }
+    }
+  }
+
+  fn encoder() -> Encoder<Event> {
+    Encoder::new()
+      .variant(
+        "created",
+        Arc::new(JSONEncoder::new()),
+        |e: &Event| match e {
+          Event::Created(v) => Some(v),
+          _ => None,
+        },
+      )
+      .variant(
+        "deleted",
+        Arc::new(JSONEncoder::new()),
+        |e: &Event| match e {
+          Event::Deleted(v) => Some(v),
+          _ => None,
+        },
+      )
+  }
+
+  fn decoder() -> Decoder<Event> {
+    Decoder::new()
+      .variant("created", Arc::new(JSONDecoder::new()), Event::Created)
+      .variant("deleted", Arc::new(JSONDecoder::new()), Event::Deleted)
+  }
+
+  #[test]
+  fn test_roundtrip() {
+    let (encoder, decoder) = (encoder(), decoder());
+    let events = vec![
+      Event::Created(TestEntity::new(1, "created")),
+      Event::Deleted(1),
+    ];
+
+    for event in events {
+      let encoded = encoder.encode(&event).unwrap();
+      assert_eq!(decoder.decode(encoded).unwrap(), event);
+    }
+  }
+
+  #[test]
+  fn test_header() {
+    let encoded = encoder().encode(&Event::Deleted(42)).unwrap();
+
+    assert_eq!(&encoded[..], b"\x07deleted42");
+  }
+
+  #[test]
+  fn test_unknown_type_decode() {
+    let result = decoder().decode(Bytes::from_static(b"\x07renamed{}"));
+
+    assert!(matches!(
+      result,
+      Err(TaggedError::UnknownType(tag)) if tag == "renamed"
+    ));
+  }
+
+  #[test]
+  fn test_unknown_type_encode() {
+    let encoder = Encoder::new().variant(
+      "created",
+      Arc::new(JSONEncoder::new()),
+      |e: &Event| match e {
+        Event::Created(v) => Some(v),
+        _ => None,
+      },
+    );
+
+    let result = encoder.encode(&Event::Deleted(1));
+
+    assert!(matches!(
+      result,
+      Err(TaggedError::UnknownType(tag)) if tag == "deleted"
+    ));
+  }
+
+  #[test]
+  fn test_variant_mismatch() {
+    let encoder = Encoder::new().variant(
+      "deleted",
+      Arc::new(JSONEncoder::<TestEntity>::new()),
+      |e: &Event| match e {
+        Event::Created(v) => Some(v),
+        _ => None,
+      },
+    );
+
+    let result = encoder.encode(&Event::Deleted(1));
+
+    assert!(matches!(result, Err(TaggedError::VariantMismatch(_))));
+  }
+
+  #[test]
+  fn test_malformed_header() {
+    let empty = decoder().decode(Bytes::new());
+    let truncated = decoder().decode(Bytes::from_static(b"\x07del"));
+
+    assert!(matches!(empty, Err(TaggedError::MalformedHeader)));
+    assert!(matches!(truncated, Err(TaggedError::MalformedHeader)));
+  }
+
+  #[test]
+  fn test_decode_error() {
+    let result = decoder().decode(Bytes::from_static(b"\x07deleted{"));
+
+    assert!(matches!(
+      result,
+      Err(TaggedError::Decode { type_tag, .. }) if type_tag == "deleted"
+    ));
+  }
+}
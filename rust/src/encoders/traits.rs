@@ -5,17 +5,16 @@
 //!
 //! # Traits
 //!
-//! - [`Encoder`]: Encodes items into byte sequences using serde serialization.
-//! - [`Decoder`]: Decodes byte sequences back into items using serde deserialization.
+//! - [`Encoder`]: Encodes items into byte sequences, optionally appending them
+//!   to a reusable buffer.
+//! - [`Decoder`]: Decodes byte sequences back into items.
 //!
 //! Both traits are object-safe, allowing for dynamic dispatch through trait objects.
 //! They require associated types to implement `Send + Sync` for thread-safe usage.
 
-use ::bytes::Bytes;
-use ::serde::{
-  de::{DeserializeOwned, Error as DecodeErr},
-  ser::{Error as EncodeErr, Serialize},
-};
+use ::std::error::Error as StdError;
+
+use ::bytes::{Bytes, BytesMut};
 
 #[cfg(test)]
 use ::mockall::automock;
@@ -34,21 +33,26 @@ use crate::tests::{
 ///
 /// # Associated Types
 ///
-/// * `Item` - The type being encoded. Must implement [`Serialize`] and be
-///   `Send + Sync` for thread-safe usage.
+/// * `Item` - The type being encoded. Must be `Send + Sync` for thread-safe usage.
+///   The trait does not require [`serde::Serialize`], so formats that are not
+///   based on serde (e.g. Protocol Buffers via `prost`) can be plugged in as well.
 /// * `Error` - The error type returned when encoding fails. Must implement
 ///   [`Error`](std::error::Error) and be `Send + Sync`. Each format can define its own error type
-///   (e.g., `serde_json::Error`, `rmp_serde::Error`).
+///   (e.g., `serde_json::Error`, `rmp_serde::Error`, `prost::EncodeError`).
 ///
 /// # Methods
 ///
 /// * [`encode`](Self::encode) - Serializes an item into a byte sequence.
+/// * [`encode_into`](Self::encode_into) - Serializes an item at the end of a
+///   reusable buffer. The default implementation copies the output of
+///   [`encode`](Self::encode); the built-in JSON, MessagePack, bincode, and
+///   postcard encoders write into the buffer directly.
 ///
 /// # Design Philosophy
 ///
 /// Rather than restricting you to a fixed set of formats, this library lets you implement
-/// `Encoder` for any serialization format. Built-in implementations (JSON, MessagePack) are
-/// provided as examples, but you can add CBOR, Protocol Buffers, custom binary formats, or anything else.
+/// `Encoder` for any serialization format. Built-in implementations (JSON, MessagePack,
+/// Protocol Buffers) are provided, but you can add CBOR, custom binary formats, or anything else.
 ///
 /// # Examples
 ///
@@ -114,9 +118,22 @@ use crate::tests::{
 /// ```
 #[cfg_attr(test, automock(type Item = TestEntity; type Error = MockEncErr;))]
 pub trait Encoder {
-  type Item: Serialize + Send + Sync;
-  type Error: EncodeErr + Send + Sync;
+  type Item: Send + Sync;
+  type Error: StdError + Send + Sync;
   fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error>;
+
+  /// Serializes `item` and appends it to `buf`.
+  ///
+  /// Used by [`Pub`](crate::Pub) with a [`BufferPool`](crate::BufferPool)
+  /// to avoid allocating a buffer per message.
+  fn encode_into(
+    &self,
+    item: &Self::Item,
+    buf: &mut BytesMut,
+  ) -> Result<(), Self::Error> {
+    buf.extend_from_slice(&self.encode(item)?);
+    Ok(())
+  }
 }
 
 /// A trait for decoding byte sequences back into items.
@@ -127,11 +144,12 @@ pub trait Encoder {
 ///
 /// # Associated Types
 ///
-/// * `Item` - The type being decoded. Must implement [`DeserializeOwned`] and be
-///   `Send + Sync` for thread-safe usage.
+/// * `Item` - The type being decoded. Must be `Send + Sync` for thread-safe usage.
+///   Like [`Encoder`], the trait does not require serde, so
+///   [`serde::de::DeserializeOwned`] is only needed by the serde-based formats.
 /// * `Error` - The error type returned when decoding fails. Must implement
 ///   [`Error`](std::error::Error) and be `Send + Sync`. Each format can define its own error type
-///   (e.g., `serde_json::Error`, custom parse errors).
+///   (e.g., `serde_json::Error`, `prost::DecodeError`, custom parse errors).
 ///
 /// # Methods
 ///
@@ -175,12 +193,6 @@ pub trait Encoder {
 ///
 /// impl std::error::Error for ParseError {}
 ///
-/// impl serde::de::Error for ParseError {
-///     fn custom<T: std::fmt::Display>(_msg: T) -> Self {
-///         ParseError::InvalidFormat
-///     }
-/// }
-///
 /// struct PlainTextDecoder;
 ///
 /// impl Decoder for PlainTextDecoder {
@@ -228,8 +240,8 @@ pub trait Encoder {
 
 #[cfg_attr(test, automock(type Item = TestEntity; type Error = MockDeErr;))]
 pub trait Decoder {
-  type Item: DeserializeOwned + Send + Sync;
-  type Error: DecodeErr + Send + Sync;
+  type Item: Send + Sync;
+  type Error: StdError + Send + Sync;
   fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error>;
 }
 
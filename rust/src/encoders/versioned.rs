@@ -0,0 +1,484 @@
+//! Versioned envelope encoder and decoder with upcasting.
+//!
+//! Messages published before an event type changed stay in the streams, so
+//! consumers have to keep reading old payloads after the type evolved. The
+//! [`Encoder`] prefixes each payload with the schema version of the item,
+//! and the [`Decoder`] decodes each version with its own decoder and then
+//! runs the registered upcasters (v1 → v2 → v3 ...) so that subscribers
+//! always receive the latest type.
+//!
+//! Payloads published before the versioned codec was adopted have no version
+//! header. [`Decoder::unversioned`] makes them readable by decoding payloads
+//! without a known header as a registered legacy version.
+//!
+//! # Wire Format
+//!
+//! | Bytes  | Content                                |
+//! |--------|----------------------------------------|
+//! | 0..4   | Schema version (big-endian `u32`)      |
+//! | 4..    | Payload produced by the inner encoder  |
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use serde::{Serialize, Deserialize};
+//! use object_transfer::encoders::{Encoder, Decoder};
+//! use object_transfer::encoders::{JSONEncoder, JSONDecoder};
+//! use object_transfer::encoders::versioned;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct UserV1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! struct UserV2 {
+//!     first_name: String,
+//!     last_name: String,
+//! }
+//!
+//! let decoder = versioned::Decoder::new(1, Arc::new(JSONDecoder::<UserV1>::new()))
+//!   .upcast(2, Arc::new(JSONDecoder::<UserV2>::new()), |v1: UserV1| {
+//!     let (first, last) = v1.name.split_once(' ').unwrap_or((&v1.name, ""));
+//!     UserV2 { first_name: first.to_string(), last_name: last.to_string() }
+//!   });
+//!
+//! // A message published before the type changed.
+//! let old = versioned::Encoder::new(1, Arc::new(JSONEncoder::new()))
+//!   .encode(&UserV1 { name: "Jane Doe".to_string() })?;
+//!
+//! assert_eq!(
+//!   decoder.decode(old)?,
+//!   UserV2 { first_name: "Jane".to_string(), last_name: "Doe".to_string() },
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::std::collections::HashMap;
+use ::std::error::Error as StdError;
+use ::std::sync::Arc;
+
+use ::bytes::{BufMut, Bytes, BytesMut};
+use ::thiserror::Error;
+
+use super::traits::{Decoder as DecoderTrait, Encoder as EncoderTrait};
+
+/// Length of the version header.
+const HEADER_LEN: usize = 4;
+
+/// Error type for the versioned decoder.
+#[derive(Error, Debug)]
+pub enum VersionedError {
+  /// The payload is shorter than the version header.
+  #[error("Payload too short for the version header: {0} bytes")]
+  TruncatedHeader(usize),
+  /// No decoder is registered for the version found in the payload.
+  #[error("Unknown schema version: {0}")]
+  UnknownVersion(u32),
+  /// The decoder registered for the version failed.
+  #[error("Decoding version {version} failed: {source}")]
+  Decode {
+    version: u32,
+    source: Box<dyn StdError + Send + Sync>,
+  },
+}
+
+/// Encoder prefixing the payloads of an inner encoder with a schema version.
+///
+/// Encoding errors are the ones of the inner encoder.
+pub struct Encoder<T, E: StdError + Send + Sync> {
+  version: u32,
+  inner: Arc<dyn EncoderTrait<Item = T, Error = E> + Send + Sync>,
+}
+
+impl<T, E: StdError + Send + Sync> Encoder<T, E> {
+  /// Creates a new versioned encoder.
+  ///
+  /// # Parameters
+  /// - `version`: Schema version of the encoded items.
+  /// - `inner`: Encoder producing the payload.
+  pub fn new(
+    version: u32,
+    inner: Arc<dyn EncoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> Self {
+    Self { version, inner }
+  }
+}
+
+impl<T, E> EncoderTrait for Encoder<T, E>
+where
+  T: Send + Sync,
+  E: StdError + Send + Sync,
+{
+  type Item = T;
+  type Error = E;
+
+  fn encode(&self, item: &Self::Item) -> Result<Bytes, Self::Error> {
+    let payload = self.inner.encode(item)?;
+    let mut buf = BytesMut::with_capacity(HEADER_LEN + payload.len());
+    buf.put_u32(self.version);
+    buf.put_slice(&payload);
+    Ok(buf.freeze())
+  }
+}
+
+type DecodeFn<T> =
+  Box<dyn Fn(Bytes) -> Result<T, VersionedError> + Send + Sync>;
+
+/// Decoder reading versioned payloads and upcasting them to the latest type.
+///
+/// The decoder is built from the oldest version to the latest one: [`new`]
+/// registers the first version, and each call to [`upcast`] registers the
+/// next version together with the function converting the previous type
+/// into the new one. Payloads of an old version are decoded with the decoder
+/// of their version and passed through every subsequent upcaster.
+///
+/// [`new`]: Decoder::new
+/// [`upcast`]: Decoder::upcast
+pub struct Decoder<T> {
+  latest: u32,
+  unversioned: Option<u32>,
+  versions: HashMap<u32, DecodeFn<T>>,
+}
+
+impl<T: Send + Sync + 'static> Decoder<T> {
+  /// Creates a decoder for the first version of the type.
+  ///
+  /// # Parameters
+  /// - `version`: Schema version of `T`.
+  /// - `decoder`: Decoder for payloads of that version.
+  pub fn new<E>(
+    version: u32,
+    decoder: Arc<dyn DecoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> Self
+  where
+    E: StdError + Send + Sync + 'static,
+  {
+    let mut versions = HashMap::new();
+    versions.insert(version, Self::decode_fn(version, decoder));
+    Self {
+      latest: version,
+      unversioned: None,
+      versions,
+    }
+  }
+
+  /// Registers the next version of the type.
+  ///
+  /// # Parameters
+  /// - `version`: Schema version of `N`. Must be greater than the latest
+  ///   registered version.
+  /// - `decoder`: Decoder for payloads of that version.
+  /// - `upcaster`: Converts items of the previous version into `N`.
+  ///
+  /// # Returns
+  /// A decoder yielding `N`, reading all the versions registered so far.
+  ///
+  /// # Panics
+  /// Panics if `version` isn't greater than the latest registered version.
+  pub fn upcast<N, E, F>(
+    self,
+    version: u32,
+    decoder: Arc<dyn DecoderTrait<Item = N, Error = E> + Send + Sync>,
+    upcaster: F,
+  ) -> Decoder<N>
+  where
+    N: Send + Sync + 'static,
+    E: StdError + Send + Sync + 'static,
+    F: Fn(T) -> N + Send + Sync + 'static,
+  {
+    assert!(
+      version > self.latest,
+      "version {version} must be greater than the latest version {}",
+      self.latest,
+    );
+    let upcaster = Arc::new(upcaster);
+    let mut versions: HashMap<u32, DecodeFn<N>> = self
+      .versions
+      .into_iter()
+      .map(|(v, decode)| {
+        let upcaster = upcaster.clone();
+        let decode: DecodeFn<N> =
+          Box::new(move |data| decode(data).map(|item| upcaster(item)));
+        (v, decode)
+      })
+      .collect();
+    versions.insert(version, Decoder::decode_fn(version, decoder));
+    Decoder {
+      latest: version,
+      unversioned: self.unversioned,
+      versions,
+    }
+  }
+
+  /// Decodes payloads without a known version header as a legacy version.
+  ///
+  /// Payloads published before the versioned codec was adopted start with
+  /// the inner payload itself, e.g. `{"` for JSON, which doesn't read as a
+  /// registered version. With this fallback, payloads that are shorter than
+  /// the header or whose header names no registered version are decoded as
+  /// a whole with the decoder of `version`, then upcast. Disabled by
+  /// default.
+  ///
+  /// Only enable it when no registered version can collide with the first
+  /// bytes of the legacy payloads.
+  ///
+  /// # Parameters
+  /// - `version`: Registered version the headerless payloads belong to.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  ///
+  /// # Panics
+  /// Panics if `version` isn't registered.
+  pub fn unversioned(mut self, version: u32) -> Self {
+    assert!(
+      self.versions.contains_key(&version),
+      "version {version} must be registered",
+    );
+    self.unversioned = Some(version);
+    self
+  }
+
+  /// Returns the latest registered version.
+  pub fn latest_version(&self) -> u32 {
+    self.latest
+  }
+
+  fn decode_fn<E>(
+    version: u32,
+    decoder: Arc<dyn DecoderTrait<Item = T, Error = E> + Send + Sync>,
+  ) -> DecodeFn<T>
+  where
+    E: StdError + Send + Sync + 'static,
+  {
+    Box::new(move |data| {
+      decoder.decode(data).map_err(|e| VersionedError::Decode {
+        version,
+        source: Box::new(e),
+      })
+    })
+  }
+}
+
+impl<T: Send + Sync + 'static> DecoderTrait for Decoder<T> {
+  type Item = T;
+  type Error = VersionedError;
+
+  fn decode(&self, data: Bytes) -> Result<Self::Item, Self::Error> {
+    let legacy = self.unversioned.and_then(|v| self.versions.get(&v));
+    if data.len() < HEADER_LEN {
+      return match legacy {
+        Some(decode) => decode(data),
+        None => Err(VersionedError::TruncatedHeader(data.len())),
+      };
+    }
+    let version = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+    match (self.versions.get(&version), legacy) {
+      (Some(decode), _) => decode(data.slice(HEADER_LEN..)),
+      (None, Some(decode)) => decode(data),
+      (None, None) => Err(VersionedError::UnknownVersion(version)),
+    }
+  }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod test {
+  use ::serde::{Deserialize, Serialize};
+
+  use crate::encoders::{JSONDecoder, JSONEncoder};
+  use crate::tests::entity::TestEntity;
+
+  use super::*;
+
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  struct TestEntityV2 {
+    id: u32,
+    name: String,
+    email: Option<String>,
+  }
+
+  #[derive(Debug, PartialEq, Serialize, Deserialize)]
+  struct TestEntityV3 {
+    id: u64,
+    name: String,
+    email: String,
+  }
+
+  fn decoder() -> Decoder<TestEntityV3> {
+    Decoder::new(1, Arc::new(JSONDecoder::<TestEntity>::new()))
+      .upcast(
+        2,
+        Arc::new(JSONDecoder::<TestEntityV2>::new()),
+        |v1: TestEntity| TestEntityV2 {
+          id: v1.id,
+          name: v1.name,
+          email: None,
+        },
+      )
+      .upcast(
+        3,
+        Arc::new(JSONDecoder::<TestEntityV3>::new()),
+        |v2: TestEntityV2| TestEntityV3 {
+          id: v2.id.into(),
+          name: v2.name,
+          email: v2.email.unwrap_or_default(),
+        },
+      )
+  }
+
+  #[test]
+  fn test_header() {
+    let encoder = Encoder::new(258, Arc::new(JSONEncoder::new()));
+
+    let encoded = encoder.encode(&TestEntity::new(1, "a")).unwrap();
+
+    assert_eq!(&encoded[..HEADER_LEN], &[0, 0, 1, 2]);
+    assert_eq!(encoded[HEADER_LEN], b'{');
+  }
+
+  #[test]
+  fn test_upcast_chain() {
+    let decoder = decoder();
+    let v1 = Encoder::new(1, Arc::new(JSONEncoder::new()))
+      .encode(&TestEntity::new(1, "v1"))
+      .unwrap();
+    let v2 = Encoder::new(2, Arc::new(JSONEncoder::new()))
+      .encode(&TestEntityV2 {
+        id: 2,
+        name: "v2".to_string(),
+        email: Some("v2@example.com".to_string()),
+      })
+      .unwrap();
+
+    assert_eq!(decoder.latest_version(), 3);
+    assert_eq!(
+      decoder.decode(v1).unwrap(),
+      TestEntityV3 {
+        id: 1,
+        name: "v1".to_string(),
+        email: String::new(),
+      }
+    );
+    assert_eq!(
+      decoder.decode(v2).unwrap(),
+      TestEntityV3 {
+        id: 2,
+        name: "v2".to_string(),
+        email: "v2@example.com".to_string(),
+      }
+    );
+  }
+
+  #[test]
+  fn test_latest_version() {
+    let entity = TestEntityV3 {
+      id: 3,
+      name: "v3".to_string(),
+      email: "v3@example.com".to_string(),
+    };
+    let encoded = Encoder::new(3, Arc::new(JSONEncoder::new()))
+      .encode(&entity)
+      .unwrap();
+
+    assert_eq!(decoder().decode(encoded).unwrap(), entity);
+  }
+
+  #[test]
+  fn test_unknown_version() {
+    let result = decoder().decode(Bytes::from_static(&[0, 0, 0, 4, b'{']));
+
+    assert!(matches!(result, Err(VersionedError::UnknownVersion(4))));
+  }
+
+  #[test]
+  fn test_truncated_header() {
+    let result = decoder().decode(Bytes::from_static(&[0, 1]));
+
+    assert!(matches!(result, Err(VersionedError::TruncatedHeader(2))));
+  }
+
+  #[test]
+  fn test_decode_error() {
+    let result = decoder().decode(Bytes::from_static(&[0, 0, 0, 2, b'{']));
+
+    assert!(matches!(
+      result,
+      Err(VersionedError::Decode { version: 2, .. })
+    ));
+  }
+
+  #[test]
+  fn test_unversioned_payload() {
+    let decoder = decoder().unversioned(1);
+    let legacy = JSONEncoder::new()
+      .encode(&TestEntity::new(1, "v0"))
+      .unwrap();
+
+    assert_eq!(
+      decoder.decode(legacy).unwrap(),
+      TestEntityV3 {
+        id: 1,
+        name: "v0".to_string(),
+        email: String::new(),
+      }
+    );
+  }
+
+  #[test]
+  fn test_unversioned_keeps_headers() {
+    let decoder = decoder().unversioned(1);
+    let v2 = Encoder::new(2, Arc::new(JSONEncoder::new()))
+      .encode(&TestEntityV2 {
+        id: 2,
+        name: "v2".to_string(),
+        email: Some("v2@example.com".to_string()),
+      })
+      .unwrap();
+
+    assert_eq!(decoder.decode(v2).unwrap().email, "v2@example.com");
+  }
+
+  #[test]
+  fn test_unversioned_short_payload() {
+    let result = decoder().unversioned(1).decode(Bytes::from_static(b"{}"));
+
+    assert!(matches!(
+      result,
+      Err(VersionedError::Decode { version: 1, .. })
+    ));
+  }
+
+  #[test]
+  fn test_headerless_payload_without_fallback() {
+    let legacy = JSONEncoder::new()
+      .encode(&TestEntity::new(1, "v0"))
+      .unwrap();
+
+    let result = decoder().decode(legacy);
+
+    assert!(matches!(result, Err(VersionedError::UnknownVersion(_))));
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_unregistered_unversioned() {
+    let _ = decoder().unversioned(4);
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_non_increasing_version() {
+    let _ = Decoder::new(2, Arc::new(JSONDecoder::<TestEntity>::new()))
+      .upcast(
+        1,
+        Arc::new(JSONDecoder::<TestEntityV2>::new()),
+        |v: TestEntity| TestEntityV2 {
+          id: v.id,
+          name: v.name,
+          email: None,
+        },
+      );
+  }
+}
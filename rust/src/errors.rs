@@ -1,19 +1,24 @@
 //! Error definitions shared across the crate.
-//! Defines high-level error types (AckError, PubError, SubError, UnSubError)
+//! Defines high-level error types (AckError, PubError, SubError, UnSubError,
+//! DispatchError, ValidationError)
 //! that use BrokerError as a common wrapper for NATS, JetStream, and
 //! serialization errors.
 
 mod ack;
 mod decode;
+mod dispatch;
 mod encode;
 mod r#pub;
 mod sub;
 mod unsub;
+mod validation;
 
 pub use self::ack::AckError;
 pub use self::decode::DecodeError;
+pub use self::dispatch::DispatchError;
 pub use self::encode::EncodeError;
 pub use self::r#pub::PubError;
 pub use self::sub::SubError;
 pub use self::unsub::UnSubError;
+pub use self::validation::{ValidationError, Violation};
 pub use crate::brokers::errors::BrokerError;
@@ -1,7 +1,7 @@
 //! Decoding error types for object deserialization.
 //!
 //! This module provides error handling for decoding failures that occur during
-//! deserialization of objects from various formats (JSON, msgpack, etc.).
+//! deserialization of objects from various formats (JSON, msgpack, protobuf, etc.).
 //! The [`DecodeError`] struct wraps underlying format-specific errors into a
 //! unified error type that can be propagated through the application.
 //!
@@ -14,7 +14,7 @@
 //! let json_err: DecodeError<serde_json::Error> = serde_json::from_str::<i32>("invalid").unwrap_err().into();
 //! ```
 
-use ::serde::de::Error as DeErr;
+use ::std::error::Error as StdError;
 
 use thiserror::Error;
 
@@ -28,15 +28,25 @@ use thiserror::Error;
 ///
 /// The underlying error is boxed to allow different serialization formats to
 /// contribute their own error types without requiring a large enum variant.
-pub struct DecodeError<E: DeErr + Send + Sync> {
+pub struct DecodeError<E: StdError + Send + Sync> {
   kind: E,
 }
 
-impl<E: DeErr + Send + Sync> DecodeError<E> {
+impl<E: StdError + Send + Sync> DecodeError<E> {
   /// Creates a new `DecodeError` from any error that implements `std::error::Error`.
   pub(crate) fn new(err: E) -> Self {
     DecodeError { kind: err }
   }
+
+  /// Returns the underlying format-specific error.
+  pub fn kind(&self) -> &E {
+    &self.kind
+  }
+
+  /// Consumes the error and returns the underlying format-specific error.
+  pub fn into_kind(self) -> E {
+    self.kind
+  }
 }
 
 /// Converts JSON deserialization errors into [`DecodeError`].
@@ -58,3 +68,128 @@ impl From<rmp_serde::decode::Error> for DecodeError<rmp_serde::decode::Error> {
     DecodeError { kind: err }
   }
 }
+
+/// Converts Protocol Buffers decoding errors into [`DecodeError`].
+///
+/// This conversion is only available when the `protobuf` feature is enabled.
+#[cfg(feature = "protobuf")]
+impl From<prost::DecodeError> for DecodeError<prost::DecodeError> {
+  fn from(err: prost::DecodeError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts bincode decoding errors into [`DecodeError`].
+///
+/// This conversion is only available when the `bincode` feature is enabled.
+#[cfg(feature = "bincode")]
+impl From<bincode::error::DecodeError>
+  for DecodeError<bincode::error::DecodeError>
+{
+  fn from(err: bincode::error::DecodeError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts postcard deserialization errors into [`DecodeError`].
+///
+/// This conversion is only available when the `postcard` feature is enabled.
+#[cfg(feature = "postcard")]
+impl From<postcard::Error> for DecodeError<postcard::Error> {
+  fn from(err: postcard::Error) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts Avro decoding errors into [`DecodeError`].
+///
+/// This conversion is only available when the `avro` feature is enabled.
+#[cfg(feature = "avro")]
+impl From<crate::encoders::avro::AvroError>
+  for DecodeError<crate::encoders::avro::AvroError>
+{
+  fn from(err: crate::encoders::avro::AvroError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts CloudEvents decoding errors into [`DecodeError`].
+///
+/// This conversion is only available when the `cloudevents` feature is enabled.
+#[cfg(feature = "cloudevents")]
+impl From<crate::encoders::cloudevents::CloudEventsError>
+  for DecodeError<crate::encoders::cloudevents::CloudEventsError>
+{
+  fn from(err: crate::encoders::cloudevents::CloudEventsError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts JSON Schema validating decoder errors into [`DecodeError`].
+///
+/// This conversion is only available when the `jsonschema` feature is enabled.
+#[cfg(feature = "jsonschema")]
+impl From<crate::encoders::json_schema::JsonSchemaError>
+  for DecodeError<crate::encoders::json_schema::JsonSchemaError>
+{
+  fn from(err: crate::encoders::json_schema::JsonSchemaError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts raw decoder errors into [`DecodeError`].
+impl From<crate::encoders::raw::RawError>
+  for DecodeError<crate::encoders::raw::RawError>
+{
+  fn from(err: crate::encoders::raw::RawError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts codec registry errors into [`DecodeError`].
+impl From<crate::encoders::registry::CodecError>
+  for DecodeError<crate::encoders::registry::CodecError>
+{
+  fn from(err: crate::encoders::registry::CodecError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts KV change decoding errors into [`DecodeError`].
+///
+/// This conversion is only available when the `nats` feature is enabled.
+#[cfg(feature = "nats")]
+impl From<crate::brokers::nats::KvError>
+  for DecodeError<crate::brokers::nats::KvError>
+{
+  fn from(err: crate::brokers::nats::KvError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts multi-format encoder and decoder errors into [`DecodeError`].
+impl From<crate::encoders::multi_format::MultiFormatError>
+  for DecodeError<crate::encoders::multi_format::MultiFormatError>
+{
+  fn from(err: crate::encoders::multi_format::MultiFormatError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts versioned decoder errors into [`DecodeError`].
+impl From<crate::encoders::versioned::VersionedError>
+  for DecodeError<crate::encoders::versioned::VersionedError>
+{
+  fn from(err: crate::encoders::versioned::VersionedError) -> Self {
+    DecodeError { kind: err }
+  }
+}
+
+/// Converts type-tagged encoder and decoder errors into [`DecodeError`].
+impl From<crate::encoders::tagged::TaggedError>
+  for DecodeError<crate::encoders::tagged::TaggedError>
+{
+  fn from(err: crate::encoders::tagged::TaggedError) -> Self {
+    DecodeError { kind: err }
+  }
+}
@@ -0,0 +1,42 @@
+use ::std::error::Error as StdError;
+
+use ::thiserror::Error;
+
+use super::ack::AckError;
+use super::decode::DecodeError;
+use super::sub::SubError;
+use crate::encoders::tagged::TaggedError;
+
+/// Error type for dispatching type-tagged items to their handlers.
+#[derive(Error, Debug)]
+pub enum DispatchError {
+  /// No handler or decoder is registered for the type tag.
+  #[error("Unknown type: {0:?}")]
+  UnknownType(String),
+  /// The handler registered for the type tag failed.
+  #[error("Handler for type {type_tag:?} failed: {source}")]
+  HandlerError {
+    type_tag: String,
+    source: Box<dyn StdError + Send + Sync>,
+  },
+  /// Subscription error other than an unknown type.
+  #[error("Subscription error: {0}")]
+  SubError(SubError<TaggedError>),
+  /// Acknowledgment error.
+  #[error("Acknowledgment error: {0}")]
+  AckError(#[from] AckError),
+}
+
+/// Converts subscription errors into [`DispatchError`], reporting payloads
+/// with an unregistered type tag as [`DispatchError::UnknownType`].
+impl From<SubError<TaggedError>> for DispatchError {
+  fn from(err: SubError<TaggedError>) -> Self {
+    match err {
+      SubError::DecodeError(err) => match err.into_kind() {
+        TaggedError::UnknownType(type_tag) => Self::UnknownType(type_tag),
+        kind => Self::SubError(SubError::DecodeError(DecodeError::new(kind))),
+      },
+      err => Self::SubError(err),
+    }
+  }
+}
@@ -2,9 +2,10 @@
 //!
 //! This module provides the [`EncodeError`] type for representing errors that occur during
 //! serialization of messages. It includes implementations to convert from various serialization
-//! format errors (JSON via serde_json and MessagePack via rmp_serde) into a unified error type.
+//! format errors (JSON via serde_json, MessagePack via rmp_serde, Protocol Buffers via prost,
+//! bincode, postcard, Avro and CloudEvents) into a unified error type.
 
-use ::serde::ser::Error as EncErr;
+use ::std::error::Error as StdError;
 
 use ::thiserror::Error;
 
@@ -16,11 +17,11 @@ use ::thiserror::Error;
 /// implementations.
 #[derive(Error, Debug)]
 #[error("encoding error: {kind}")]
-pub struct EncodeError<E: EncErr + Send + Sync> {
+pub struct EncodeError<E: StdError + Send + Sync> {
   kind: E,
 }
 
-impl<E: EncErr + Send + Sync> EncodeError<E> {
+impl<E: StdError + Send + Sync> EncodeError<E> {
   /// Creates a new `EncodeError` from any error that implements `std::error::Error`.
   pub(crate) fn new(err: E) -> Self {
     EncodeError { kind: err }
@@ -40,3 +41,76 @@ impl From<rmp_serde::encode::Error> for EncodeError<rmp_serde::encode::Error> {
     EncodeError { kind: err }
   }
 }
+
+#[cfg(feature = "protobuf")]
+impl From<prost::EncodeError> for EncodeError<prost::EncodeError> {
+  fn from(err: prost::EncodeError) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+#[cfg(feature = "bincode")]
+impl From<::bincode::error::EncodeError>
+  for EncodeError<::bincode::error::EncodeError>
+{
+  fn from(err: ::bincode::error::EncodeError) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+#[cfg(feature = "postcard")]
+impl From<postcard::Error> for EncodeError<postcard::Error> {
+  fn from(err: postcard::Error) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+#[cfg(feature = "avro")]
+impl From<crate::encoders::avro::AvroError>
+  for EncodeError<crate::encoders::avro::AvroError>
+{
+  fn from(err: crate::encoders::avro::AvroError) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+#[cfg(feature = "cloudevents")]
+impl From<crate::encoders::cloudevents::CloudEventsError>
+  for EncodeError<crate::encoders::cloudevents::CloudEventsError>
+{
+  fn from(err: crate::encoders::cloudevents::CloudEventsError) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+impl From<crate::encoders::registry::CodecError>
+  for EncodeError<crate::encoders::registry::CodecError>
+{
+  fn from(err: crate::encoders::registry::CodecError) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+impl From<crate::encoders::multi_format::MultiFormatError>
+  for EncodeError<crate::encoders::multi_format::MultiFormatError>
+{
+  fn from(err: crate::encoders::multi_format::MultiFormatError) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+impl From<crate::encoders::versioned::VersionedError>
+  for EncodeError<crate::encoders::versioned::VersionedError>
+{
+  fn from(err: crate::encoders::versioned::VersionedError) -> Self {
+    EncodeError { kind: err }
+  }
+}
+
+impl From<crate::encoders::tagged::TaggedError>
+  for EncodeError<crate::encoders::tagged::TaggedError>
+{
+  fn from(err: crate::encoders::tagged::TaggedError) -> Self {
+    EncodeError { kind: err }
+  }
+}
@@ -1,17 +1,22 @@
-use ::serde::ser::Error as EncErr;
+use ::std::error::Error as StdError;
+
 use ::thiserror::Error;
 
 use super::BrokerError;
 use super::encode::EncodeError;
+use super::validation::ValidationError;
 
 /// Error type for publishing operations in the messaging system.
 #[derive(Error, Debug)]
-pub enum PubError<EncodeErrorType: EncErr + Send + Sync> {
+pub enum PubError<EncodeErrorType: StdError + Send + Sync> {
   /// Error during broker operations.
   #[error("Broker error: {0}")]
   BrokerError(#[from] BrokerError),
   #[error("Encoding error: {0}")]
   EncodeError(#[from] EncodeError<EncodeErrorType>),
+  /// The item was rejected by the validator before encoding.
+  #[error("Validation error: {0}")]
+  ValidationError(#[from] ValidationError),
   /// Generic error variant for miscellaneous errors (Test use only).
   #[cfg(test)]
   #[error("Error Test")]
@@ -1,13 +1,15 @@
-use ::serde::de::Error as DeErr;
+use ::std::error::Error as StdError;
+
 use ::thiserror::Error;
 
 use super::BrokerError;
 use super::ack::AckError;
 use super::decode::DecodeError;
+use super::validation::ValidationError;
 
 /// Error type for subscription operations in the messaging system.
 #[derive(Error, Debug)]
-pub enum SubError<DecodeErrorType: DeErr + Send + Sync> {
+pub enum SubError<DecodeErrorType: StdError + Send + Sync> {
   /// Broker error.
   #[error("Broker error: {0}")]
   BrokerError(#[from] BrokerError),
@@ -17,6 +19,9 @@ pub enum SubError<DecodeErrorType: DeErr + Send + Sync> {
   /// Decoding error for deserialization failures.
   #[error("Decoding error: {0}")]
   DecodeError(#[from] DecodeError<DecodeErrorType>),
+  /// The decoded item was rejected by the validator.
+  #[error("Validation error: {0}")]
+  ValidationError(#[from] ValidationError),
   /// Generic error variant for miscellaneous errors (Test use only).
   #[cfg(test)]
   #[error("Error Test")]
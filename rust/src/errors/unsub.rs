@@ -10,4 +10,7 @@ pub enum UnSubError {
   BrokerError(#[from] BrokerError),
   #[error("No Unsubscribe handler found")]
   NoHandler,
+  /// There was no subscription to end nor anything to delete.
+  #[error("Nothing to unsubscribe")]
+  NothingToUnsubscribe,
 }
@@ -0,0 +1,198 @@
+use ::std::fmt;
+
+use ::thiserror::Error;
+
+/// A single field-level validation failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+  /// Path of the invalid field (e.g. `address.zip` or `items[0].name`).
+  /// Empty for violations of the whole item.
+  pub field: String,
+  /// Machine-readable code of the failed rule (e.g. `length`).
+  pub code: String,
+  /// Human-readable description of the failure.
+  pub message: Option<String>,
+}
+
+impl Violation {
+  /// Creates a violation without message.
+  ///
+  /// # Parameters
+  /// - `field`: Path of the invalid field.
+  /// - `code`: Code of the failed rule.
+  pub fn new(field: impl Into<String>, code: impl Into<String>) -> Self {
+    Self {
+      field: field.into(),
+      code: code.into(),
+      message: None,
+    }
+  }
+
+  /// Sets the human-readable message.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn message(mut self, message: impl Into<String>) -> Self {
+    self.message = Some(message.into());
+    self
+  }
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: {}", self.field, self.code)?;
+    if let Some(message) = &self.message {
+      write!(f, " ({message})")?;
+    }
+    Ok(())
+  }
+}
+
+/// Error type for items rejected by a validator.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("validation failed: [{}]", .violations.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+pub struct ValidationError {
+  violations: Vec<Violation>,
+}
+
+impl ValidationError {
+  /// Creates a new `ValidationError` from the given violations.
+  pub fn new(violations: Vec<Violation>) -> Self {
+    Self { violations }
+  }
+
+  /// Returns the field-level violations.
+  pub fn violations(&self) -> &[Violation] {
+    &self.violations
+  }
+}
+
+impl From<Violation> for ValidationError {
+  fn from(violation: Violation) -> Self {
+    Self::new(vec![violation])
+  }
+}
+
+#[cfg(feature = "validator")]
+fn flatten(
+  prefix: &str,
+  errors: &::validator::ValidationErrors,
+  violations: &mut Vec<Violation>,
+) {
+  use ::validator::ValidationErrorsKind;
+
+  for (field, kind) in errors.errors() {
+    let path = match (prefix.is_empty(), field.as_ref()) {
+      (_, "__all__") => prefix.to_string(),
+      (true, field) => field.to_string(),
+      (false, field) => format!("{prefix}.{field}"),
+    };
+    match kind {
+      ValidationErrorsKind::Field(errors) => {
+        violations.extend(errors.iter().map(|err| Violation {
+          field: path.clone(),
+          code: err.code.to_string(),
+          message: err.message.as_ref().map(ToString::to_string),
+        }));
+      }
+      ValidationErrorsKind::Struct(errors) => {
+        flatten(&path, errors, violations);
+      }
+      ValidationErrorsKind::List(items) => {
+        for (idx, errors) in items {
+          flatten(&format!("{path}[{idx}]"), errors, violations);
+        }
+      }
+    }
+  }
+}
+
+/// Converts errors of the `validator` crate into [`ValidationError`],
+/// flattening nested structs and lists into dotted field paths.
+///
+/// This conversion is only available when the `validator` feature is enabled.
+#[cfg(feature = "validator")]
+impl From<::validator::ValidationErrors> for ValidationError {
+  fn from(errors: ::validator::ValidationErrors) -> Self {
+    let mut violations = Vec::new();
+    flatten("", &errors, &mut violations);
+    violations.sort_by(|a, b| a.field.cmp(&b.field));
+    Self::new(violations)
+  }
+}
+
+#[cfg(all(test, feature = "validator"))]
+mod test {
+  use ::validator::{
+    Validate, ValidationError as FieldError, ValidationErrors,
+    ValidationErrorsKind,
+  };
+
+  use super::*;
+
+  #[derive(Validate)]
+  #[validate(schema(
+    function = "validate_range",
+    skip_on_field_errors = false
+  ))]
+  struct Range {
+    #[validate(range(min = 0))]
+    start: i32,
+    end: i32,
+  }
+
+  fn validate_range(range: &Range) -> Result<(), FieldError> {
+    if range.start > range.end {
+      return Err(FieldError::new("order"));
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn test_from_validator() {
+    let mut address = ValidationErrors::new();
+    address.add("zip", FieldError::new("length"));
+    let mut item = ValidationErrors::new();
+    item.add(
+      "name",
+      FieldError::new("required").with_message("name is required".into()),
+    );
+    let mut errors = ValidationErrors::new();
+    errors.add("id", FieldError::new("range"));
+    errors.errors_mut().insert(
+      "address".into(),
+      ValidationErrorsKind::Struct(Box::new(address)),
+    );
+    errors.errors_mut().insert(
+      "items".into(),
+      ValidationErrorsKind::List([(0, Box::new(item))].into()),
+    );
+
+    let err = ValidationError::from(errors);
+
+    assert_eq!(
+      err.violations(),
+      &[
+        Violation::new("address.zip", "length"),
+        Violation::new("id", "range"),
+        Violation::new("items[0].name", "required")
+          .message("name is required"),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_schema_violation_has_empty_field() {
+    let range = Range { start: -1, end: -2 };
+
+    let err = ValidationError::from(range.validate().unwrap_err());
+
+    assert_eq!(
+      err.violations(),
+      &[
+        Violation::new("", "order"),
+        Violation::new("start", "range")
+      ]
+    );
+  }
+}
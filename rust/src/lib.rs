@@ -85,6 +85,8 @@
 
 mod ack_noop;
 pub mod brokers;
+mod buffer_pool;
+mod dispatcher;
 pub mod encoders;
 pub mod errors;
 mod options;
@@ -97,8 +99,10 @@ mod unsub_noop;
 mod tests;
 
 pub use ack_noop::AckNoop;
+pub use buffer_pool::BufferPool;
+pub use dispatcher::Dispatcher;
 pub use options::SubOpt;
 pub use publisher::Pub;
 pub use subscriber::Sub;
-pub use traits::{PubTrait, SubTrait, UnSubTrait};
+pub use traits::{PubTrait, SubTrait, UnSubTrait, Validate};
 pub use unsub_noop::UnSubNoop;
@@ -9,11 +9,15 @@ use ::core::fmt::Debug;
 #[derive(Debug, Clone)]
 pub struct SubOpt {
   pub(crate) auto_ack: bool,
+  pub(crate) ack_undecodable: bool,
 }
 
 impl Default for SubOpt {
   fn default() -> Self {
-    Self { auto_ack: true }
+    Self {
+      auto_ack: true,
+      ack_undecodable: false,
+    }
   }
 }
 
@@ -37,4 +41,21 @@ impl SubOpt {
     self.auto_ack = auto_ack;
     self
   }
+
+  /// Sets whether messages whose payload fails to decode are acknowledged
+  /// before the decoding error is yielded.
+  ///
+  /// Enable it when decoding failures are permanent, e.g. payloads with a
+  /// type tag no decoder is registered for, so that the broker doesn't
+  /// redeliver them forever. Disabled by default.
+  ///
+  /// # Arguments
+  /// * `ack_undecodable` - If true, undecodable messages are acknowledged
+  ///
+  /// # Returns
+  /// The updated `SubOpt` instance
+  pub fn ack_undecodable(mut self, ack_undecodable: bool) -> Self {
+    self.ack_undecodable = ack_undecodable;
+    self
+  }
 }
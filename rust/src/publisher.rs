@@ -1,16 +1,19 @@
+use ::std::convert::Infallible;
+use ::std::error::Error as StdError;
 use ::std::marker::PhantomData;
 use ::std::sync::Arc;
 
 use async_trait::async_trait;
-use serde::Serialize;
-use serde::ser::Error as EncErr;
+use bytes::Bytes;
 
 use crate::brokers::PubBrokerTrait;
+use crate::buffer_pool::BufferPool;
 use crate::encoders::Encoder;
-use crate::errors::{EncodeError, PubError};
-use crate::traits::PubTrait;
+use crate::encoders::raw::{Encoder as RawEncoder, RawItem};
+use crate::errors::{EncodeError, PubError, ValidationError};
+use crate::traits::{PubTrait, Validate, ValidatorFn};
 
-/// Publisher for serializable messages using a pluggable encoder and context.
+/// Publisher for typed messages using a pluggable encoder and context.
 ///
 /// The publisher encodes messages using the provided [`Encoder`]
 /// and delegates the actual publish call to an injected [`PubBrokerTrait`] so it can
@@ -99,17 +102,19 @@ use crate::traits::PubTrait;
 ///   Ok(())
 /// }
 /// ```
-pub struct Pub<T, SerErr: EncErr + Send + Sync> {
+pub struct Pub<T, SerErr: StdError + Send + Sync> {
   ctx: Arc<dyn PubBrokerTrait + Send + Sync>,
   subject: String,
   encoder: Arc<dyn Encoder<Item = T, Error = SerErr> + Send + Sync>,
+  validator: Option<ValidatorFn<T>>,
+  buffer_pool: Option<Arc<BufferPool>>,
   _phantom: PhantomData<T>,
 }
 
 impl<T, SerErr> Pub<T, SerErr>
 where
-  T: Serialize + Send + Sync,
-  SerErr: EncErr + Send + Sync,
+  T: Send + Sync,
+  SerErr: StdError + Send + Sync,
 {
   /// Creates a new publisher for the given subject with a pluggable encoder.
   ///
@@ -139,16 +144,94 @@ where
       ctx,
       subject: subject.into(),
       encoder,
+      validator: None,
+      buffer_pool: None,
       _phantom: PhantomData,
     }
   }
+
+  /// Sets a function validating each item before it is encoded.
+  ///
+  /// Rejected items are not published, and [`PubError::ValidationError`]
+  /// is returned with the violations.
+  ///
+  /// # Parameters
+  /// - `validator`: Returns the violations found in the item, if any.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn validator<F>(mut self, validator: F) -> Self
+  where
+    F: Fn(&T) -> Result<(), ValidationError> + Send + Sync + 'static,
+  {
+    self.validator = Some(Arc::new(validator));
+    self
+  }
+
+  /// Validates each item with its [`Validate`] implementation before it is
+  /// encoded.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn validated(self) -> Self
+  where
+    T: Validate + 'static,
+  {
+    self.validator(T::validate)
+  }
+
+  /// Encodes items into buffers taken from `pool` instead of allocating a
+  /// buffer per message.
+  ///
+  /// Items are encoded with [`Encoder::encode_into`].
+  ///
+  /// # Parameters
+  /// - `pool`: Pool of reusable buffers, possibly shared with other
+  ///   publishers.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn buffer_pool(mut self, pool: Arc<BufferPool>) -> Self {
+    self.buffer_pool = Some(pool);
+    self
+  }
+
+  fn encode(&self, obj: &T) -> Result<Bytes, SerErr> {
+    let Some(pool) = &self.buffer_pool else {
+      return self.encoder.encode(obj);
+    };
+    let mut buf = pool.acquire();
+    let encoded = self
+      .encoder
+      .encode_into(obj, &mut buf)
+      .map(|_| buf.split().freeze());
+    pool.release(buf);
+    encoded
+  }
+}
+
+impl<T: RawItem + 'static> Pub<T, Infallible> {
+  /// Creates a publisher sending raw items as-is.
+  ///
+  /// Items ([`Bytes`], `Vec<u8>` or [`String`]) are encoded with the
+  /// [`raw`](crate::encoders::raw) encoder, bypassing serde.
+  ///
+  /// # Parameters
+  /// - `ctx`: Backend publish context that delivers the bytes.
+  /// - `subject`: Destination subject or topic to send messages to.
+  pub fn raw(
+    ctx: Arc<dyn PubBrokerTrait + Send + Sync>,
+    subject: impl Into<String>,
+  ) -> Self {
+    Self::new(ctx, subject, Arc::new(RawEncoder::new()))
+  }
 }
 
 #[async_trait]
 impl<T, SerErr> PubTrait for Pub<T, SerErr>
 where
-  T: Serialize + Send + Sync,
-  SerErr: EncErr + Send + Sync,
+  T: Send + Sync,
+  SerErr: StdError + Send + Sync,
 {
   type Item = T;
   type EncodeErr = SerErr;
@@ -158,7 +241,10 @@ where
   /// # Parameters
   /// - `obj`: The typed value to encode and send to the subject.
   async fn publish(&self, obj: &T) -> Result<(), PubError<Self::EncodeErr>> {
-    let payload = self.encoder.encode(obj).map_err(|e| EncodeError::new(e))?;
+    if let Some(validator) = &self.validator {
+      validator(obj)?;
+    }
+    let payload = self.encode(obj).map_err(|e| EncodeError::new(e))?;
     self
       .ctx
       .publish(self.subject.as_str(), payload.into())
@@ -174,6 +260,7 @@ mod tests {
 
   use crate::brokers::{errors::BrokerError, traits::MockPubBrokerTrait};
   use crate::encoders::MockEncoder;
+  use crate::errors::Violation;
   use crate::tests::entity::TestEntity;
   use crate::tests::error::{MockBrokerErr, MockEncErr};
 
@@ -229,4 +316,90 @@ mod tests {
         .to_string()
     );
   }
+
+  #[tokio::test]
+  async fn test_publish_validation_error() {
+    let entity = TestEntity::new(1, "");
+    let mut ctx = MockPubBrokerTrait::new();
+    ctx.expect_publish().never();
+    let mut encoder = MockEncoder::new();
+    encoder.expect_encode().never();
+    let publisher: Pub<TestEntity, _> =
+      Pub::new(Arc::new(ctx), "test.subject.invalid", Arc::new(encoder))
+        .validated();
+    let res = publisher.publish(&entity).await;
+    assert!(matches!(
+      res,
+      Err(PubError::ValidationError(err))
+        if err.violations() == [Violation::new("name", "required")]
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_publish_custom_validator() {
+    let entity = TestEntity::new(1, "Test Name");
+    let mut ctx = MockPubBrokerTrait::new();
+    ctx.expect_publish().never();
+    let mut encoder = MockEncoder::new();
+    encoder.expect_encode().never();
+    let publisher: Pub<TestEntity, _> =
+      Pub::new(Arc::new(ctx), "test.subject.custom", Arc::new(encoder))
+        .validator(|entity: &TestEntity| {
+          if entity.id < 10 {
+            return Err(Violation::new("id", "range").into());
+          }
+          Ok(())
+        });
+    let res = publisher.publish(&entity).await;
+    assert!(matches!(res, Err(PubError::ValidationError(_))));
+  }
+
+  #[tokio::test]
+  async fn test_publish_with_buffer_pool() {
+    let entity = TestEntity::new(1, "Test Name");
+    let subject = "test.subject.pooled";
+    let mut ctx = MockPubBrokerTrait::new();
+    ctx
+      .expect_publish()
+      .with(eq(subject), eq(Bytes::from("serialized bytes")))
+      .times(2)
+      .returning(|_, _| Ok(()));
+    let mut encoder = MockEncoder::new();
+    encoder.expect_encode().never();
+    encoder
+      .expect_encode_into()
+      .with(eq(entity.clone()), always())
+      .times(2)
+      .returning(|_, buf| {
+        buf.extend_from_slice(b"serialized bytes");
+        Ok(())
+      });
+    let pool = Arc::new(BufferPool::new());
+    let publisher: Pub<TestEntity, _> =
+      Pub::new(Arc::new(ctx), subject, Arc::new(encoder))
+        .buffer_pool(pool.clone());
+
+    publisher.publish(&entity).await.unwrap();
+    publisher.publish(&entity).await.unwrap();
+
+    assert_eq!(pool.len(), 1);
+  }
+
+  #[tokio::test]
+  async fn test_publish_raw() {
+    let mut ctx = MockPubBrokerTrait::new();
+    ctx
+      .expect_publish()
+      .with(
+        eq("test.subject.raw"),
+        eq(Bytes::from_static(b"raw payload")),
+      )
+      .times(1)
+      .returning(|_, _| Ok(()));
+    let publisher = Pub::raw(Arc::new(ctx), "test.subject.raw");
+
+    let res = publisher.publish(&Bytes::from_static(b"raw payload")).await;
+
+    assert!(res.is_ok());
+  }
 }
@@ -1,16 +1,17 @@
+use std::error::Error as StdError;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use futures::stream::BoxStream;
 use futures::{TryFutureExt, TryStreamExt};
-use serde::de::{DeserializeOwned, Error as DeErr};
 
 use crate::brokers::SubBrokerTrait;
 use crate::encoders::Decoder;
-use crate::errors::{DecodeError, SubError, UnSubError};
+use crate::encoders::raw::{Decoder as RawDecoder, RawError, RawItem};
+use crate::errors::{DecodeError, SubError, UnSubError, ValidationError};
 use crate::options::SubOpt;
-use crate::traits::{AckTrait, SubTrait, UnSubTrait};
+use crate::traits::{AckTrait, SubTrait, UnSubTrait, Validate, ValidatorFn};
 
 /// Subscriber wrapper that deserializes messages and optionally acknowledges them.
 ///
@@ -103,12 +104,6 @@ use crate::traits::{AckTrait, SubTrait, UnSubTrait};
 ///
 /// impl std::error::Error for CustomError {}
 ///
-/// impl serde::de::Error for CustomError {
-///   fn custom<T: std::fmt::Display>(_msg: T) -> Self {
-///     CustomError::InvalidData
-///   }
-/// }
-///
 /// impl Decoder for CustomDecoder {
 ///   type Item = MyType;
 ///   type Error = CustomError;
@@ -120,18 +115,19 @@ use crate::traits::{AckTrait, SubTrait, UnSubTrait};
 ///   }
 /// }
 /// ```
-pub struct Sub<T, DecodeErrorType: DeErr + Send + Sync> {
+pub struct Sub<T, DecodeErrorType: StdError + Send + Sync> {
   ctx: Arc<dyn SubBrokerTrait + Send + Sync>,
   unsub: Arc<dyn UnSubTrait + Send + Sync>,
   decoder: Arc<dyn Decoder<Item = T, Error = DecodeErrorType> + Send + Sync>,
   options: SubOpt,
+  validator: Option<ValidatorFn<T>>,
   _marker: PhantomData<T>,
 }
 
 impl<T, DecodeErrorType> Sub<T, DecodeErrorType>
 where
-  T: DeserializeOwned + Send + Sync,
-  DecodeErrorType: DeErr + Send + Sync,
+  T: Send + Sync,
+  DecodeErrorType: StdError + Send + Sync,
 {
   /// Creates a new subscriber using the provided context, decoder, and options.
   ///
@@ -164,16 +160,70 @@ where
       unsub,
       decoder,
       options,
+      validator: None,
       _marker: PhantomData,
     }
   }
+
+  /// Sets a function validating each item after it is decoded.
+  ///
+  /// Rejected items are yielded as [`SubError::ValidationError`] with the
+  /// violations. Since validating them again would fail again, their
+  /// messages are acknowledged before the error is yielded, even when
+  /// auto-acknowledgment is disabled, so that the broker doesn't redeliver
+  /// them.
+  ///
+  /// # Parameters
+  /// - `validator`: Returns the violations found in the item, if any.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn validator<F>(mut self, validator: F) -> Self
+  where
+    F: Fn(&T) -> Result<(), ValidationError> + Send + Sync + 'static,
+  {
+    self.validator = Some(Arc::new(validator));
+    self
+  }
+
+  /// Validates each item with its [`Validate`] implementation after it is
+  /// decoded.
+  ///
+  /// # Returns
+  /// Self for method chaining
+  pub fn validated(self) -> Self
+  where
+    T: Validate + 'static,
+  {
+    self.validator(T::validate)
+  }
+}
+
+impl<T: RawItem + 'static> Sub<T, RawError> {
+  /// Creates a subscriber receiving payloads as raw items.
+  ///
+  /// Payloads are decoded into [`Bytes`](::bytes::Bytes), `Vec<u8>` or
+  /// [`String`] with the [`raw`](crate::encoders::raw) decoder, bypassing
+  /// serde.
+  ///
+  /// # Parameters
+  /// - `ctx`: Message retrieval context responsible for producing raw bytes.
+  /// - `unsub`: Unsubscribe handler to cancel the subscription when requested.
+  /// - `options`: Subscription behavior such as auto-acknowledgment settings.
+  pub fn raw(
+    ctx: Arc<dyn SubBrokerTrait + Send + Sync>,
+    unsub: Arc<dyn UnSubTrait + Send + Sync>,
+    options: SubOpt,
+  ) -> Self {
+    Self::new(ctx, unsub, Arc::new(RawDecoder::new()), options)
+  }
 }
 
 #[async_trait]
 impl<T, DecodeErrorType> SubTrait for Sub<T, DecodeErrorType>
 where
-  T: DeserializeOwned + Send + Sync,
-  DecodeErrorType: DeErr + Send + Sync,
+  T: Send + Sync,
+  DecodeErrorType: StdError + Send + Sync,
 {
   type Item = T;
   type DecodeErr = DecodeErrorType;
@@ -193,10 +243,21 @@ where
   > {
     let messages = self.ctx.subscribe().await?.map_err(SubError::from);
     let stream = messages.and_then(async move |(msg, acker)| {
-      let data = self
-        .decoder
-        .decode(msg)
-        .map_err(|e| SubError::from(DecodeError::new(e)))?;
+      let data = match self.decoder.decode(msg) {
+        Ok(data) => data,
+        Err(e) => {
+          if self.options.ack_undecodable {
+            acker.ack().map_err(SubError::AckError).await?;
+          }
+          return Err(SubError::from(DecodeError::new(e)));
+        }
+      };
+      if let Some(validator) = &self.validator
+        && let Err(err) = validator(&data)
+      {
+        acker.ack().map_err(SubError::AckError).await?;
+        return Err(err.into());
+      }
       if self.options.auto_ack {
         acker.ack().map_err(|e| SubError::AckError(e)).await?;
       }
@@ -209,8 +270,8 @@ where
 #[async_trait]
 impl<T, DecodeErrorType> UnSubTrait for Sub<T, DecodeErrorType>
 where
-  T: DeserializeOwned + Send + Sync,
-  DecodeErrorType: DeErr + Send + Sync,
+  T: Send + Sync,
+  DecodeErrorType: StdError + Send + Sync,
 {
   /// Invokes the configured unsubscribe handler.
   async fn unsubscribe(&self) -> Result<(), UnSubError> {
@@ -226,7 +287,7 @@ mod test {
 
   use crate::UnSubNoop;
   use crate::encoders::MockDecoder;
-  use crate::errors::AckError;
+  use crate::errors::{AckError, Violation};
   use crate::tests::{
     entity::TestEntity, error::MockDeErr, subscribe::SubscribeMock,
   };
@@ -330,4 +391,59 @@ mod test {
       vec![SubError::<MockDeErr>::AckError(AckError::ErrorTest).to_string()]
     );
   }
+
+  #[tokio::test]
+  async fn test_validation_err() {
+    let data: Vec<(Bytes, Arc<dyn AckTrait + Send + Sync>)> =
+      vec![(Bytes::new(), {
+        let mut ack_mock = MockAckTrait::new();
+        ack_mock.expect_ack().returning(|| Ok(())).once();
+        Arc::new(ack_mock)
+      })];
+    let ctx: Arc<dyn SubBrokerTrait + Send + Sync> =
+      Arc::new(SubscribeMock::new(data));
+    let mut decoder = MockDecoder::new();
+    decoder
+      .expect_decode()
+      .once()
+      .returning(|_| Ok(TestEntity::new(0, "")));
+    let subscribe: Sub<TestEntity, _> = Sub::new(
+      ctx,
+      Arc::new(UnSubNoop::new(false)),
+      Arc::new(decoder),
+      SubOpt::new().auto_ack(false),
+    )
+    .validated();
+    let stream = subscribe.subscribe().await.unwrap();
+    let obtained: Vec<_> = stream.collect().await;
+    assert!(matches!(
+      &obtained[..],
+      [Err(SubError::ValidationError(err))]
+        if err.violations() == [Violation::new("name", "required")]
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_subscribe_raw() {
+    let mut ack = MockAckTrait::new();
+    ack.expect_ack().returning(|| Ok(())).once();
+    let data: Vec<(Bytes, Arc<dyn AckTrait + Send + Sync>)> =
+      vec![(Bytes::from_static(b"text"), Arc::new(ack))];
+    let subscribe: Sub<String, _> = Sub::raw(
+      Arc::new(SubscribeMock::new(data)),
+      Arc::new(UnSubNoop::new(false)),
+      SubOpt::new(),
+    );
+
+    let obtained: Vec<String> = subscribe
+      .subscribe()
+      .await
+      .unwrap()
+      .map_ok(|(text, _ack)| text)
+      .try_collect()
+      .await
+      .unwrap();
+
+    assert_eq!(obtained, vec!["text".to_string()]);
+  }
 }
@@ -1,5 +1,8 @@
 use ::serde::{Deserialize, Serialize};
 
+use crate::errors::{ValidationError, Violation};
+use crate::traits::Validate;
+
 #[derive(
   Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize,
 )]
@@ -16,3 +19,12 @@ impl TestEntity {
     }
   }
 }
+
+impl Validate for TestEntity {
+  fn validate(&self) -> Result<(), ValidationError> {
+    if self.name.is_empty() {
+      return Err(Violation::new("name", "required").into());
+    }
+    Ok(())
+  }
+}
@@ -3,7 +3,6 @@ use ::std::sync::Arc;
 use ::async_trait::async_trait;
 use ::bytes::Bytes;
 use ::futures::stream::{BoxStream, StreamExt, iter};
-use ::serde::de::DeserializeOwned;
 
 use crate::brokers::SubBrokerTrait;
 use crate::errors::{BrokerError, SubError};
@@ -24,7 +23,7 @@ impl<Entity> SubscribeMock<Entity> {
 #[async_trait]
 impl<Entity> SubTrait for SubscribeMock<Entity>
 where
-  Entity: DeserializeOwned + Clone + Send + Sync,
+  Entity: Clone + Send + Sync,
 {
   type Item = Entity;
   type DecodeErr = MockDeErr;
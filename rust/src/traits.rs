@@ -7,12 +7,19 @@
 //!
 //! # Core Traits
 //!
-//! - [`PubTrait`]: Publish strongly-typed items that implement [`serde::Serialize`]. Handles encoding
-//!   and delivery to the backing message broker.
-//! - [`SubTrait`]: Subscribe to a stream of strongly-typed items that implement [`serde::de::DeserializeOwned`].
-//!   Returns a stream of decoded messages paired with acknowledgment handles.
+//! - [`PubTrait`]: Publish strongly-typed items. Handles encoding and delivery to the backing
+//!   message broker.
+//! - [`SubTrait`]: Subscribe to a stream of strongly-typed items. Returns a stream of decoded
+//!   messages paired with acknowledgment handles.
 //! - [`AckTrait`]: Acknowledge receipt of a message after it has been successfully processed.
 //! - [`UnSubTrait`]: Cancel an active subscription gracefully.
+//! - [`Validate`]: Check items before they are published and after they are
+//!   received.
+//!
+//! Neither [`PubTrait`] nor [`SubTrait`] requires the items to implement serde
+//! traits; the bounds are left to the [`crate::encoders::Encoder`] and
+//! [`crate::encoders::Decoder`] in use, so serde-based formats and others such
+//! as Protocol Buffers fit alike.
 //!
 //! # Dispatch Patterns
 //!
@@ -178,16 +185,15 @@
 //! - [`UnSubTrait::unsubscribe()`] returns [`crate::errors::UnSubError`]
 //!
 
+use ::std::error::Error as StdError;
 use ::std::sync::Arc;
 
 use ::async_trait::async_trait;
 use ::futures::stream::BoxStream;
-use ::serde::{
-  de::{DeserializeOwned, Error as DeErr},
-  ser::{Error as EncErr, Serialize},
-};
 
-use crate::errors::{AckError, PubError, SubError, UnSubError};
+use crate::errors::{
+  AckError, PubError, SubError, UnSubError, ValidationError,
+};
 
 #[cfg(test)]
 use crate::tests::{entity::TestEntity, error::MockDeErr, error::MockEncErr};
@@ -200,9 +206,9 @@ use ::mockall::automock;
 #[cfg_attr(test, automock(type Item = TestEntity; type EncodeErr = MockEncErr;))]
 #[async_trait]
 pub trait PubTrait {
-  type Item: Serialize + Send + Sync;
-  type EncodeErr: EncErr + Send + Sync;
-  /// Publish a serializable item through the implementor.
+  type Item: Send + Sync;
+  type EncodeErr: StdError + Send + Sync;
+  /// Publish a typed item through the implementor.
   ///
   /// # Parameters
   /// - `obj`: The typed item to serialize and send to the backing transport.
@@ -222,8 +228,8 @@ pub trait AckTrait {
 /// Subscription interface returning a stream of decoded items and ack handles.
 #[async_trait]
 pub trait SubTrait {
-  type Item: DeserializeOwned + Send + Sync;
-  type DecodeErr: DeErr + Send + Sync;
+  type Item: Send + Sync;
+  type DecodeErr: StdError + Send + Sync;
   async fn subscribe(
     &self,
   ) -> Result<
@@ -237,6 +243,42 @@ pub trait SubTrait {
   >;
 }
 
+/// Field-level validation of items.
+///
+/// Pass [`Pub::validated`](crate::Pub::validated) or
+/// [`Sub::validated`](crate::Sub::validated) to reject invalid items before
+/// they are encoded, or after they are decoded.
+///
+/// With the `validator` feature, errors of the `validator` crate convert into
+/// [`ValidationError`], so an implementation can simply delegate to a
+/// `#[derive(validator::Validate)]` with `Ok(validator::Validate::validate(self)?)`.
+///
+/// ```rust
+/// use object_transfer::errors::{ValidationError, Violation};
+/// use object_transfer::traits::Validate;
+///
+/// struct Event {
+///   name: String,
+/// }
+///
+/// impl Validate for Event {
+///   fn validate(&self) -> Result<(), ValidationError> {
+///     if self.name.is_empty() {
+///       return Err(Violation::new("name", "required").into());
+///     }
+///     Ok(())
+///   }
+/// }
+/// ```
+pub trait Validate {
+  /// Returns the violations found in the item, if any.
+  fn validate(&self) -> Result<(), ValidationError>;
+}
+
+/// Validation function run by publishers and subscribers.
+pub(crate) type ValidatorFn<T> =
+  Arc<dyn Fn(&T) -> Result<(), ValidationError> + Send + Sync>;
+
 /// Allows canceling a subscription.
 #[cfg_attr(test, automock)]
 #[async_trait]